@@ -26,6 +26,10 @@ fn main() {
     window.set_cursor_mode(CursorMode::Disabled);
     window.make_current();
 
+    let crosshair = glfw.create_cursor(&crosshair_image(16), 8, 8)
+        .expect("Failed to create GLFW cursor.");
+    window.set_cursor(Some(crosshair));
+
     window.set_cursor_pos_polling(true);
     window.set_key_polling(true);
 
@@ -37,6 +41,19 @@ fn main() {
     }
 }
 
+/// Creates a square image containing a white crosshair on a transparent
+/// background.
+fn crosshair_image(size: u32) -> glfw::PixelImage {
+    let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let alpha = if x == size / 2 || y == size / 2 { 255 } else { 0 };
+            pixels.extend([255, 255, 255, alpha].iter().cloned());
+        }
+    }
+    glfw::PixelImage { width: size, height: size, pixels: pixels }
+}
+
 fn handle_window_event(window: &mut glfw::Window, event: glfw::WindowEvent) {
     match event {
         glfw::WindowEvent::CursorPos(xpos, ypos) => println!("Cursor position: ({:?}, {:?})", xpos, ypos),
@@ -18,11 +18,11 @@ extern crate glfw;
 use glfw::Context;
 
 fn main() {
-    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    let glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
 
-    glfw.window_hint(glfw::WindowHint::Visible(true));
-
-    let (mut window, _) = glfw.create_window(640, 480, "Defaults", glfw::WindowMode::Windowed)
+    let (mut window, _) = glfw::WindowBuilder::new(&glfw, 640, 480, "Defaults")
+        .hint(glfw::WindowHint::Visible(true))
+        .create()
         .expect("Failed to create GLFW window.");
 
     window.make_current();
@@ -38,10 +38,12 @@ fn main() {
     window.set_framebuffer_size_polling(true);
     window.set_key_polling(true);
     window.set_char_polling(true);
+    window.set_char_mods_polling(true);
     window.set_mouse_button_polling(true);
     window.set_cursor_pos_polling(true);
     window.set_cursor_enter_polling(true);
     window.set_scroll_polling(true);
+    window.set_drop_polling(true);
 
     // Alternatively, all event types may be set to poll at once. Note that
     // in this example, this call is redundant as all events have been set
@@ -68,13 +70,18 @@ fn handle_window_event(window: &mut glfw::Window, (time, event): (f64, glfw::Win
         glfw::WindowEvent::Focus(false)                   => println!("Time: {:?}, Window focus lost.", time),
         glfw::WindowEvent::Iconify(true)                  => println!("Time: {:?}, Window was minimised", time),
         glfw::WindowEvent::Iconify(false)                 => println!("Time: {:?}, Window was maximised.", time),
+        glfw::WindowEvent::Maximize(true)                 => println!("Time: {:?}, Window was maximized.", time),
+        glfw::WindowEvent::Maximize(false)                => println!("Time: {:?}, Window was restored.", time),
         glfw::WindowEvent::FramebufferSize(w, h)          => println!("Time: {:?}, Framebuffer size: ({:?}, {:?})", time, w, h),
+        glfw::WindowEvent::ContentScale(x, y)             => println!("Time: {:?}, Content scale: ({:?}, {:?})", time, x, y),
         glfw::WindowEvent::Char(character)                => println!("Time: {:?}, Character: {:?}", time, character),
+        glfw::WindowEvent::CharModifiers(character, mods) => println!("Time: {:?}, Character: {:?}, Modifiers: [{:?}]", time, character, mods),
         glfw::WindowEvent::MouseButton(btn, action, mods) => println!("Time: {:?}, Button: {:?}, Action: {:?}, Modifiers: [{:?}]", time, glfw::DebugAliases(btn), action, mods),
         glfw::WindowEvent::CursorPos(xpos, ypos)          => window.set_title(&format!("Time: {:?}, Cursor position: ({:?}, {:?})", time, xpos, ypos)),
         glfw::WindowEvent::CursorEnter(true)              => println!("Time: {:?}, Cursor entered window.", time),
         glfw::WindowEvent::CursorEnter(false)             => println!("Time: {:?}, Cursor left window.", time),
         glfw::WindowEvent::Scroll(x, y)                   => window.set_title(&format!("Time: {:?}, Scroll offset: ({:?}, {:?})", time, x, y)),
+        glfw::WindowEvent::FileDrop(paths)                => println!("Time: {:?}, Files dropped: {:?}", time, paths),
         glfw::WindowEvent::Key(key, scancode, action, mods) => {
             println!("Time: {:?}, Key: {:?}, ScanCode: {:?}, Action: {:?}, Modifiers: [{:?}]", time, key, scancode, action, mods);
             match (key, action) {
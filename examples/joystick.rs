@@ -0,0 +1,60 @@
+// Copyright 2013 The GLFW-RS Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate glfw;
+
+use glfw::{Action, Context, Key};
+
+fn main() {
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+
+    let (mut window, events) = glfw.create_window(300, 300, "Joystick Test", glfw::WindowMode::Windowed)
+        .expect("Failed to create GLFW window.");
+
+    window.set_key_polling(true);
+    window.make_current();
+
+    let (mut poller, joystick_events) = glfw::JoystickPoller::new(&glfw);
+    poller.set_dead_zone(0.1);
+    poller.set_axis_threshold(0.05);
+
+    while !window.should_close() {
+        glfw.poll_events();
+        poller.poll();
+        for (_, event) in glfw::flush_messages(&events) {
+            handle_window_event(&mut window, event);
+        }
+        for (time, event) in glfw::flush_messages(&joystick_events) {
+            match event {
+                glfw::JoystickInputEvent::Button(id, button, action) => {
+                    println!("Time: {:?}, Joystick: {:?}, Button: {:?}, Action: {:?}", time, id, button, action)
+                }
+                glfw::JoystickInputEvent::AxisMoved(id, axis, value) => {
+                    println!("Time: {:?}, Joystick: {:?}, Axis: {:?}, Value: {:?}", time, id, axis, value)
+                }
+            }
+        }
+        window.swap_buffers();
+    }
+}
+
+fn handle_window_event(window: &mut glfw::Window, event: glfw::WindowEvent) {
+    match event {
+        glfw::WindowEvent::Key(Key::Escape, _, Action::Press, _) => {
+            window.set_should_close(true)
+        }
+        _ => {}
+    }
+}
@@ -22,7 +22,7 @@ fn main() {
 
     glfw.with_connected_monitors(|_, monitors| {
         for monitor in monitors.iter() {
-            println!("{:?}: {:?}", monitor.get_name(), monitor.get_video_mode());
+            println!("{:?}: {:?}, DPI: {:?}", monitor.get_name(), monitor.get_video_mode(), monitor.dpi());
         }
     });
 
@@ -31,6 +31,8 @@ fn main() {
             m.map_or(glfw::WindowMode::Windowed, |m| glfw::WindowMode::FullScreen(m)))
     }).expect("Failed to create GLFW window.");
 
+    let monitor_events = glfw.monitor_events();
+
     window.set_key_polling(true);
     window.make_current();
 
@@ -39,6 +41,10 @@ fn main() {
         for (_, event) in glfw::flush_messages(&events) {
             handle_window_event(&mut window, event);
         }
+        for (_, event, handle) in glfw::flush_messages(&monitor_events) {
+            let name = handle.with_monitor(|m| m.get_name());
+            println!("{:?}: {:?} {:?}", handle, event, name);
+        }
     }
 }
 
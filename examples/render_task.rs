@@ -14,7 +14,8 @@
 // limitations under the License.
 
 //! Demonstrates how concurrent rendering can be achieved
-//! through the use of `RenderContext`s.
+//! through the use of `RenderContext`s, and how the render task can wake up
+//! the main thread while it is blocked waiting for events.
 
 extern crate glfw;
 
@@ -32,17 +33,23 @@ fn main() {
 
     let render_context = window.render_context();
     let (send, recv) = channel();
+    let (wakeup, frames) = glfw.create_wakeup_channel();
 
     let render_task = Builder::new().name("render task".to_string());
     let render_task_done = render_task.spawn(move || {
-        render(render_context, recv);
+        render(render_context, recv, wakeup);
     });
 
     while !window.should_close() {
-        glfw.poll_events();
+        // Sleep until either a window event arrives or the render task
+        // reports progress.
+        glfw.wait_events();
         for (_, event) in glfw::flush_messages(&events) {
             handle_window_event(&mut window, event);
         }
+        for frame_count in glfw::flush_messages(&frames) {
+            println!("Rendered {:?} frames", frame_count);
+        }
     }
 
     // Tell the render task to exit.
@@ -52,8 +59,9 @@ fn main() {
     let _ = render_task_done;
 }
 
-fn render(mut context: glfw::RenderContext, finish: Receiver<()>) {
+fn render(mut context: glfw::RenderContext, finish: Receiver<()>, wakeup: glfw::WakeupSender<u64>) {
     context.make_current();
+    let mut frame_count = 0u64;
     loop {
         // Check if the rendering should stop.
         if finish.try_recv() == Ok(()) { break };
@@ -61,6 +69,12 @@ fn render(mut context: glfw::RenderContext, finish: Receiver<()>) {
         // Perform rendering calls
 
         context.swap_buffers();
+
+        // Periodically report progress to the main thread.
+        frame_count += 1;
+        if frame_count % 100 == 0 {
+            let _ = wakeup.send(frame_count);
+        }
     }
 
     // required on some platforms
@@ -15,96 +15,406 @@
 
 //! Private callback support functions.
 
-use libc::{c_double, c_int, c_uint};
+use libc::{c_char, c_double, c_float, c_int, c_uint};
 use std::mem;
+use std::path::PathBuf;
+use std::slice;
 use std::sync::mpsc::Sender;
 
 use super::*;
 
-macro_rules! callback(
-    (
-        type Args = ($($arg:ident: $arg_ty:ty),*);
-        type Callback = $Callback:ident;
-        let ext_set = $ext_set:expr;
-        fn callback($($ext_arg:ident: $ext_arg_ty:ty),*) $call:expr
-    ) => (
-        thread_local!(static CALLBACK_KEY: RefCell<Option<Box<Object<Args> + 'static>>> = RefCell::new(None));
+pub mod error {
+    use libc::{c_int, c_char};
+    use std::cell::{Cell, RefCell};
+    use std::mem;
+    use std::sync::{Mutex, MutexGuard, Once, ONCE_INIT};
+
+    // The error callback is written out by hand. GLFW reports errors on
+    // whichever thread raised them, so the user callback is stored globally
+    // rather than in thread-local storage. Errors can also be diverted by
+    // `capture`, which is per-thread. The low-level callback is therefore
+    // always installed, even when no user callback is set.
+    //
+    // The user callback is called by one thread at a time, with other threads
+    // waiting on `CALL` until it returns. It is taken out of its slot while it
+    // runs, so that it can itself set the callback without deadlocking.
+    // Errors raised by the callback itself are queued, and passed to the
+    // callback once it has returned.
+
+    type Callback = Box<FnMut(::Error, String) + Send>;
 
-        type Args = ($($arg_ty),*,);
+    struct Slot {
+        callback: Option<Callback>,
+        /// Incremented whenever the callback is set or unset, so that a
+        /// callback that was taken out to be called is not put back after
+        /// being replaced.
+        generation: usize,
+    }
+
+    static mut CALLBACK: *mut Mutex<Slot> = 0 as *mut Mutex<Slot>;
+    static mut CALL: *mut Mutex<()> = 0 as *mut Mutex<()>;
+    static mut CALLBACK_INIT: Once = ONCE_INIT;
+
+    thread_local!(static CAPTURED_KEY: RefCell<Option<Vec<(::Error, String)>>> = RefCell::new(None));
+
+    // Set while this thread is calling the user callback, along with the
+    // errors raised by the callback in the meantime.
+    thread_local!(static CALLING_KEY: Cell<bool> = Cell::new(false));
+    thread_local!(static PENDING_KEY: RefCell<Vec<(::Error, String)>> = RefCell::new(Vec::new()));
+
+    fn init() {
+        unsafe {
+            CALLBACK_INIT.call_once(|| {
+                CALLBACK = mem::transmute(Box::new(Mutex::new(Slot { callback: None, generation: 0 })));
+                CALL = mem::transmute(Box::new(Mutex::new(())));
+            });
+        }
+    }
 
-        trait Object<T> {
-            fn call(&self, args: T);
+    /// Locks a mutex, ignoring poisoning caused by a callback that panicked.
+    fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+        match mutex.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
         }
+    }
+
+    /// Locks the global callback.
+    fn lock_callback() -> MutexGuard<'static, Slot> {
+        init();
+        unsafe { lock(&*CALLBACK) }
+    }
+
+    /// Locks the right to call the callback.
+    fn lock_call() -> MutexGuard<'static, ()> {
+        init();
+        unsafe { lock(&*CALL) }
+    }
+
+    fn replace(f: Option<Callback>) {
+        let mut slot = lock_callback();
+        slot.callback = f;
+        slot.generation += 1;
+    }
+
+    pub fn set(f: Callback) {
+        replace(Some(f));
+        install();
+    }
 
-        impl<UserData> Object<Args> for ::Callback<fn($($arg_ty),*, &UserData), UserData> {
-            fn call(&self, ($($arg),*,): Args) {
-                (self.f)($($arg),*, &self.data);
+    pub fn unset() {
+        replace(None);
+        install();
+    }
+
+    fn install() {
+        unsafe { ::ffi::glfwSetErrorCallback(Some(callback as extern "C" fn(c_int, *const c_char))); }
+    }
+
+    /// Calls `f`, collecting the errors that are reported on this thread in
+    /// the meantime instead of passing them to the error callback.
+    pub fn capture<T, F: FnOnce() -> T>(f: F) -> (T, Vec<(::Error, String)>) {
+        let previous = CAPTURED_KEY.with(|captured| {
+            mem::replace(&mut *captured.borrow_mut(), Some(Vec::new()))
+        });
+        let result = f();
+        let errors = CAPTURED_KEY.with(|captured| {
+            mem::replace(&mut *captured.borrow_mut(), previous)
+        });
+        (result, errors.unwrap_or(Vec::new()))
+    }
+
+    /// Clears the flag marking this thread as calling the callback, even if
+    /// the callback panics.
+    struct Calling;
+
+    impl Calling {
+        fn start() -> Calling {
+            CALLING_KEY.with(|calling| calling.set(true));
+            Calling
+        }
+    }
+
+    impl Drop for Calling {
+        fn drop(&mut self) {
+            CALLING_KEY.with(|calling| calling.set(false));
+            PENDING_KEY.with(|pending| pending.borrow_mut().clear());
+        }
+    }
+
+    /// Holds the callback while it is being called, and puts it back into
+    /// its slot afterwards, even if the callback panics. It is not put back
+    /// if the callback was replaced in the meantime.
+    struct Taken {
+        callback: Option<Callback>,
+        generation: usize,
+    }
+
+    impl Drop for Taken {
+        fn drop(&mut self) {
+            let mut slot = lock_callback();
+            if slot.generation == self.generation {
+                slot.callback = self.callback.take();
             }
         }
+    }
 
-        pub fn set<UserData: 'static>(f: ::$Callback<UserData>) {
-            let mut boxed_cb = Some(Box::new(f) as Box<Object<Args> + 'static>);
-            CALLBACK_KEY.with(|cb| {
-                *cb.borrow_mut() = boxed_cb.take();
-            });
-            ($ext_set)(Some(callback as extern "C" fn($($ext_arg: $ext_arg_ty),*)));
+    fn call(error: ::Error, description: String) {
+        let mut taken = {
+            let mut slot = lock_callback();
+            Taken { callback: slot.callback.take(), generation: slot.generation }
+        };
+        if let Some(ref mut cb) = taken.callback {
+            cb(error, description);
         }
+    }
 
-        pub fn unset() {
-            CALLBACK_KEY.with(|cb| {
-                *cb.borrow_mut() = None;
+    /// Passes an error to the error callback, if one is set.
+    pub fn report(error: ::Error, description: String) {
+        if CALLING_KEY.with(|calling| calling.get()) {
+            // The error was raised by the callback itself.
+            PENDING_KEY.with(|pending| pending.borrow_mut().push((error, description)));
+            return;
+        }
+        let _call = lock_call();
+        let _calling = Calling::start();
+        let mut next = Some((error, description));
+        while let Some((error, description)) = next {
+            call(error, description);
+            next = PENDING_KEY.with(|pending| {
+                let mut pending = pending.borrow_mut();
+                if pending.is_empty() { None } else { Some(pending.remove(0)) }
             });
-            ($ext_set)(None);
         }
+    }
 
-        extern "C" fn callback($($ext_arg: $ext_arg_ty),*) {
-            CALLBACK_KEY.with(|cb| {
-                match *cb.borrow() {
-                    Some(ref cb) => unsafe { cb.call($call) },
-                    _ => {}
-                }
-            })
+    extern "C" fn callback(error: c_int, description: *const c_char) {
+        let error: ::Error = unsafe { mem::transmute(error) };
+        let description = unsafe { ::string_from_c_str(description) };
+        let uncaptured = CAPTURED_KEY.with(|captured| {
+            match *captured.borrow_mut() {
+                Some(ref mut errors) => { errors.push((error, description)); None },
+                None => Some((error, description)),
+            }
+        });
+        if let Some((error, description)) = uncaptured {
+            report(error, description);
         }
-    )
-);
+    }
+}
 
-pub mod error {
-    use libc::{c_int, c_char};
+pub mod monitor {
+    use libc::{c_int};
     use std::cell::RefCell;
     use std::mem;
+    use std::sync::{Mutex, MutexGuard, Once, ONCE_INIT};
+    use std::sync::mpsc::Sender;
 
-    callback!(
-        type Args = (error: ::Error, description: String);
-        type Callback = ErrorCallback;
-        let ext_set = |cb| unsafe { ::ffi::glfwSetErrorCallback(cb) };
-        fn callback(error: c_int, description: *const c_char) {
-            (mem::transmute(error), ::string_from_c_str(description))
+    // The monitor callback is written out by hand, as the low-level callback
+    // also keeps track of the connected monitors so that `MonitorHandle`s can
+    // be invalidated when a monitor is disconnected. It is therefore always
+    // installed once GLFW has been initialized, even when no user callback is
+    // set. The handles are tracked globally, so that a handle obtained on any
+    // thread is invalidated.
+
+    trait Object {
+        fn call(&self, monitor: ::Monitor, event: ::MonitorEvent);
+    }
+
+    impl<UserData> Object for ::MonitorCallback<UserData> {
+        fn call(&self, monitor: ::Monitor, event: ::MonitorEvent) {
+            (self.f)(monitor, event, &self.data);
         }
-    );
+    }
+
+    thread_local!(static CALLBACK_KEY: RefCell<Option<Box<Object + 'static>>> = RefCell::new(None));
+
+    struct Handles {
+        /// The monitors that handles have been given out for, along with
+        /// their ids.
+        monitors: Vec<(usize, *mut ::ffi::GLFWmonitor)>,
+        next_id: usize,
+    }
+
+    static mut HANDLES: *mut Mutex<Handles> = 0 as *mut Mutex<Handles>;
+    static mut HANDLES_INIT: Once = ONCE_INIT;
+
+    /// Locks the global handle registry, ignoring poisoning.
+    fn lock_handles() -> MutexGuard<'static, Handles> {
+        unsafe {
+            HANDLES_INIT.call_once(|| {
+                HANDLES = mem::transmute(Box::new(Mutex::new(Handles { monitors: Vec::new(), next_id: 0 })));
+            });
+            match (*HANDLES).lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            }
+        }
+    }
+
+    thread_local!(static SENDER_KEY: RefCell<Option<Sender<(f64, ::MonitorEvent, ::MonitorHandle)>>> = RefCell::new(None));
+
+    pub fn set<UserData: 'static>(f: ::MonitorCallback<UserData>) {
+        let mut boxed_cb = Some(Box::new(f) as Box<Object + 'static>);
+        CALLBACK_KEY.with(|cb| {
+            *cb.borrow_mut() = boxed_cb.take();
+        });
+    }
+
+    pub fn unset() {
+        CALLBACK_KEY.with(|cb| {
+            *cb.borrow_mut() = None;
+        });
+    }
+
+    pub fn set_sender(sender: Sender<(f64, ::MonitorEvent, ::MonitorHandle)>) {
+        SENDER_KEY.with(|s| {
+            *s.borrow_mut() = Some(sender);
+        });
+    }
+
+    pub fn install() {
+        unsafe { ::ffi::glfwSetMonitorCallback(Some(callback as extern "C" fn(*mut ::ffi::GLFWmonitor, c_int))); }
+    }
+
+    /// Returns the id of the handle for a connected monitor, allocating a new
+    /// one if none has been given out yet.
+    pub fn handle_id(ptr: *mut ::ffi::GLFWmonitor) -> usize {
+        let mut handles = lock_handles();
+        match handles.monitors.iter().find(|&&(_, p)| p == ptr) {
+            Some(&(id, _)) => return id,
+            None => {}
+        }
+        let id = handles.next_id;
+        handles.next_id += 1;
+        handles.monitors.push((id, ptr));
+        id
+    }
+
+    /// Returns the monitor a handle refers to, or `None` if the monitor has
+    /// since been disconnected.
+    pub fn handle_ptr(id: usize) -> Option<*mut ::ffi::GLFWmonitor> {
+        lock_handles().monitors.iter().find(|&&(i, _)| i == id).map(|&(_, ptr)| ptr)
+    }
+
+    extern "C" fn callback(monitor: *mut ::ffi::GLFWmonitor, event: c_int) {
+        let event: ::MonitorEvent = unsafe { mem::transmute(event) };
+        SENDER_KEY.with(|s| {
+            let mut s = s.borrow_mut();
+            let disconnected = match *s {
+                Some(ref sender) => {
+                    let time = unsafe { ::ffi::glfwGetTime() as f64 };
+                    let handle = ::MonitorHandle::from_ptr(monitor);
+                    sender.send((time, event, handle)).is_err()
+                }
+                None => false,
+            };
+            // Stop sending events once the receiver has been dropped.
+            if disconnected {
+                *s = None;
+            }
+        });
+        CALLBACK_KEY.with(|cb| {
+            match *cb.borrow() {
+                Some(ref cb) => cb.call(::Monitor { ptr: monitor }, event),
+                _ => {}
+            }
+        });
+        // GLFW frees the monitor once the callback returns, so any handles
+        // to it are invalidated after the user callback has seen the event.
+        if event == ::MonitorEvent::Disconnected {
+            lock_handles().monitors.retain(|&(_, ptr)| ptr != monitor);
+        }
+    }
 }
 
-pub mod monitor {
+#[cfg(feature = "glfw-3-2")]
+pub mod joystick {
     use libc::{c_int};
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
     use std::mem;
+    use std::sync::mpsc::Sender;
+
+    thread_local!(static SENDER_KEY: RefCell<Option<Sender<(f64, ::JoystickId, ::JoystickEvent)>>> = RefCell::new(None));
+    thread_local!(static CONNECTED_KEY: RefCell<Option<Box<FnMut(::JoystickId, String)>>> = RefCell::new(None));
+    // Incremented whenever the connection callback is set, so that a
+    // callback that was taken out to be called is not put back after being
+    // replaced or removed.
+    thread_local!(static CONNECTED_GENERATION_KEY: Cell<usize> = Cell::new(0));
+
+    pub fn set_sender(sender: Sender<(f64, ::JoystickId, ::JoystickEvent)>) {
+        SENDER_KEY.with(|s| {
+            *s.borrow_mut() = Some(sender);
+        });
+        install();
+    }
+
+    pub fn set_connected(f: Option<Box<FnMut(::JoystickId, String)>>) {
+        CONNECTED_KEY.with(|cb| {
+            *cb.borrow_mut() = f;
+        });
+        CONNECTED_GENERATION_KEY.with(|generation| generation.set(generation.get() + 1));
+        install();
+    }
 
-    callback!(
-        type Args = (monitor: ::Monitor, event: ::MonitorEvent);
-        type Callback = MonitorCallback;
-        let ext_set = |cb| unsafe { ::ffi::glfwSetMonitorCallback(cb) };
-        fn callback(monitor: *mut ::ffi::GLFWmonitor, event: c_int) {
-            let monitor = ::Monitor {
-                ptr: monitor
+    fn install() {
+        unsafe { ::ffi::glfwSetJoystickCallback(Some(callback as extern "C" fn(c_int, c_int))); }
+    }
+
+    extern "C" fn callback(joy: c_int, event: c_int) {
+        let id: ::JoystickId = unsafe { mem::transmute(joy) };
+        let event: ::JoystickEvent = unsafe { mem::transmute(event) };
+        SENDER_KEY.with(|s| {
+            let mut s = s.borrow_mut();
+            let disconnected = match *s {
+                Some(ref sender) => {
+                    let time = unsafe { ::ffi::glfwGetTime() as f64 };
+                    sender.send((time, id, event)).is_err()
+                }
+                None => false,
             };
-            (monitor, mem::transmute(event))
+            // Stop sending events once the receiver has been dropped.
+            if disconnected {
+                *s = None;
+            }
+        });
+        if event == ::JoystickEvent::Connected {
+            // The closure is taken out of its slot while it runs, so that it
+            // can replace or remove itself.
+            let connected = CONNECTED_KEY.with(|cb| cb.borrow_mut().take());
+            let generation = CONNECTED_GENERATION_KEY.with(|generation| generation.get());
+            if let Some(mut f) = connected {
+                let name = unsafe { ::string_from_c_str(::ffi::glfwGetJoystickName(joy)) };
+                f(id, name);
+                if CONNECTED_GENERATION_KEY.with(|g| g.get()) == generation {
+                    CONNECTED_KEY.with(|cb| {
+                        *cb.borrow_mut() = Some(f);
+                    });
+                }
+            }
         }
-    );
+    }
 }
 
 unsafe fn get_sender<'a>(window: &'a *mut ffi::GLFWwindow) -> &'a Sender<(f64, WindowEvent)> {
     mem::transmute(ffi::glfwGetWindowUserPointer(*window))
 }
 
+/// Copies a path reported by GLFW. On unix the path is kept as raw bytes, as
+/// file names are not required to be valid UTF-8.
+#[cfg(unix)]
+unsafe fn path_from_c_str(c_str: *const c_char) -> PathBuf {
+    use std::ffi::{CStr, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(OsStr::from_bytes(CStr::from_ptr(c_str).to_bytes()))
+}
+
+/// Copies a path reported by GLFW, which is encoded as UTF-8.
+#[cfg(not(unix))]
+unsafe fn path_from_c_str(c_str: *const c_char) -> PathBuf {
+    PathBuf::from(::string_from_c_str(c_str))
+}
+
 // Note that this macro creates a static function pointer rather than a plain function.
 // This makes it more ergonomic to embed in an Option; see set_window_callback! in lib.rs
 macro_rules! window_callback(
@@ -116,6 +426,21 @@ macro_rules! window_callback(
             actual_callback
         };
      );
+    // GLFW frees the array of paths once the callback returns, so each path
+    // is copied into the event before it is sent.
+    (fn $name:ident ($count:ident: c_int, $paths:ident: *mut *const c_char) => $event:ident(Vec<PathBuf>)) => (
+        pub static $name: (extern "C" fn(window: *mut ffi::GLFWwindow, $count: c_int, $paths: *mut *const c_char)) = {
+            extern "C" fn actual_callback(window: *mut ffi::GLFWwindow, $count: c_int, $paths: *mut *const c_char) {
+                unsafe {
+                    let paths = slice::from_raw_parts($paths as *const *const c_char, $count as usize).iter()
+                        .map(|&p| path_from_c_str(p))
+                        .collect::<Vec<PathBuf>>();
+                    get_sender(&window).send((ffi::glfwGetTime() as f64, WindowEvent::$event(paths))).unwrap();
+                }
+            }
+            actual_callback
+        };
+     );
     (fn $name:ident ($($ext_arg:ident: $ext_arg_ty:ty),*) => $event:ident($($arg_conv:expr),*)) => (
         pub static $name: (extern "C" fn(window: *mut ffi::GLFWwindow $(, $ext_arg: $ext_arg_ty)*)) = {
             extern "C" fn actual_callback(window: *mut ffi::GLFWwindow $(, $ext_arg: $ext_arg_ty)*) {
@@ -132,10 +457,16 @@ window_callback!(fn window_close_callback()
 window_callback!(fn window_refresh_callback()                                               => Refresh);
 window_callback!(fn window_focus_callback(focused: c_int)                                   => Focus(focused == ffi::TRUE));
 window_callback!(fn window_iconify_callback(iconified: c_int)                               => Iconify(iconified == ffi::TRUE));
+#[cfg(feature = "glfw-3-3")]
+window_callback!(fn window_maximize_callback(maximized: c_int)                              => Maximize(maximized == ffi::TRUE));
 window_callback!(fn framebuffer_size_callback(width: c_int, height: c_int)                  => FramebufferSize(width as i32, height as i32));
+#[cfg(feature = "glfw-3-3")]
+window_callback!(fn window_content_scale_callback(xscale: c_float, yscale: c_float)         => ContentScale(xscale as f32, yscale as f32));
 window_callback!(fn mouse_button_callback(button: c_int, action: c_int, mods: c_int)        => MouseButton(mem::transmute(button), mem::transmute(action), Modifiers::from_bits(mods).unwrap()));
 window_callback!(fn cursor_pos_callback(xpos: c_double, ypos: c_double)                     => CursorPos(xpos as f64, ypos as f64));
 window_callback!(fn cursor_enter_callback(entered: c_int)                                   => CursorEnter(entered == ffi::TRUE));
 window_callback!(fn scroll_callback(xpos: c_double, ypos: c_double)                         => Scroll(xpos as f64, ypos as f64));
 window_callback!(fn key_callback(key: c_int, scancode: c_int, action: c_int, mods: c_int)   => Key(mem::transmute(key), scancode, mem::transmute(action), Modifiers::from_bits(mods).unwrap()));
 window_callback!(fn char_callback(character: c_uint)                                        => Char(::std::char::from_u32(character).unwrap()));
+window_callback!(fn char_mods_callback(character: c_uint, mods: c_int)                      => CharModifiers(::std::char::from_u32(character).unwrap(), Modifiers::from_bits(mods).unwrap()));
+window_callback!(fn drop_callback(num_paths: c_int, paths: *mut *const c_char)              => FileDrop(Vec<PathBuf>));
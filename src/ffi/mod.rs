@@ -158,6 +158,16 @@ pub const MOD_CONTROL                  : c_int = 0x0002;
 pub const MOD_ALT                      : c_int = 0x0004;
 pub const MOD_SUPER                    : c_int = 0x0008;
 
+pub const HAT_CENTERED                 : c_int = 0;
+pub const HAT_UP                       : c_int = 1;
+pub const HAT_RIGHT                    : c_int = 2;
+pub const HAT_DOWN                     : c_int = 4;
+pub const HAT_LEFT                     : c_int = 8;
+pub const HAT_RIGHT_UP                 : c_int = HAT_RIGHT | HAT_UP;
+pub const HAT_RIGHT_DOWN               : c_int = HAT_RIGHT | HAT_DOWN;
+pub const HAT_LEFT_UP                  : c_int = HAT_LEFT  | HAT_UP;
+pub const HAT_LEFT_DOWN                : c_int = HAT_LEFT  | HAT_DOWN;
+
 pub const JOYSTICK_1                   : c_int = 0;
 pub const JOYSTICK_2                   : c_int = 1;
 pub const JOYSTICK_3                   : c_int = 2;
@@ -176,6 +186,31 @@ pub const JOYSTICK_15                  : c_int = 14;
 pub const JOYSTICK_16                  : c_int = 15;
 pub const JOYSTICK_LAST                : c_int = JOYSTICK_16;
 
+pub const GAMEPAD_BUTTON_A             : c_int = 0;
+pub const GAMEPAD_BUTTON_B             : c_int = 1;
+pub const GAMEPAD_BUTTON_X             : c_int = 2;
+pub const GAMEPAD_BUTTON_Y             : c_int = 3;
+pub const GAMEPAD_BUTTON_LEFT_BUMPER   : c_int = 4;
+pub const GAMEPAD_BUTTON_RIGHT_BUMPER  : c_int = 5;
+pub const GAMEPAD_BUTTON_BACK          : c_int = 6;
+pub const GAMEPAD_BUTTON_START         : c_int = 7;
+pub const GAMEPAD_BUTTON_GUIDE         : c_int = 8;
+pub const GAMEPAD_BUTTON_LEFT_THUMB    : c_int = 9;
+pub const GAMEPAD_BUTTON_RIGHT_THUMB   : c_int = 10;
+pub const GAMEPAD_BUTTON_DPAD_UP       : c_int = 11;
+pub const GAMEPAD_BUTTON_DPAD_RIGHT    : c_int = 12;
+pub const GAMEPAD_BUTTON_DPAD_DOWN     : c_int = 13;
+pub const GAMEPAD_BUTTON_DPAD_LEFT     : c_int = 14;
+pub const GAMEPAD_BUTTON_LAST          : c_int = GAMEPAD_BUTTON_DPAD_LEFT;
+
+pub const GAMEPAD_AXIS_LEFT_X          : c_int = 0;
+pub const GAMEPAD_AXIS_LEFT_Y          : c_int = 1;
+pub const GAMEPAD_AXIS_RIGHT_X         : c_int = 2;
+pub const GAMEPAD_AXIS_RIGHT_Y         : c_int = 3;
+pub const GAMEPAD_AXIS_LEFT_TRIGGER    : c_int = 4;
+pub const GAMEPAD_AXIS_RIGHT_TRIGGER   : c_int = 5;
+pub const GAMEPAD_AXIS_LAST            : c_int = GAMEPAD_AXIS_RIGHT_TRIGGER;
+
 pub const MOUSE_BUTTON_1               : c_int = 0;
 pub const MOUSE_BUTTON_2               : c_int = 1;
 pub const MOUSE_BUTTON_3               : c_int = 2;
@@ -206,6 +241,7 @@ pub const VISIBLE                      : c_int = 0x00020004;
 pub const DECORATED                    : c_int = 0x00020005;
 pub const AUTO_ICONIFY                 : c_int = 0x00020006;
 pub const FLOATING                     : c_int = 0x00020007;
+pub const MAXIMIZED                    : c_int = 0x00020008;
 
 pub const RED_BITS                     : c_int = 0x00021001;
 pub const GREEN_BITS                   : c_int = 0x00021002;
@@ -222,7 +258,7 @@ pub const STEREO                       : c_int = 0x0002100C;
 pub const SAMPLES                      : c_int = 0x0002100D;
 pub const SRGB_CAPABLE                 : c_int = 0x0002100E;
 pub const REFRESH_RATE                 : c_int = 0x0002100F;
-pub const DOUBLEBUFFER                 : c_int = 0x00021010; // TODO: Not yet exposed
+pub const DOUBLEBUFFER                 : c_int = 0x00021010;
 
 pub const CLIENT_API                   : c_int = 0x00022001;
 pub const CONTEXT_VERSION_MAJOR        : c_int = 0x00022002;
@@ -232,7 +268,7 @@ pub const CONTEXT_ROBUSTNESS           : c_int = 0x00022005;
 pub const OPENGL_FORWARD_COMPAT        : c_int = 0x00022006;
 pub const OPENGL_DEBUG_CONTEXT         : c_int = 0x00022007;
 pub const OPENGL_PROFILE               : c_int = 0x00022008;
-pub const CONTEXT_RELEASE_BEHAVIOR     : c_int = 0x00022009; // TODO: Not yet exposed
+pub const CONTEXT_RELEASE_BEHAVIOR     : c_int = 0x00022009;
 
 pub const OPENGL_API                   : c_int = 0x00030001;
 pub const OPENGL_ES_API                : c_int = 0x00030002;
@@ -253,21 +289,21 @@ pub const CURSOR_NORMAL                : c_int = 0x00034001;
 pub const CURSOR_HIDDEN                : c_int = 0x00034002;
 pub const CURSOR_DISABLED              : c_int = 0x00034003;
 
-pub const ANY_RELEASE_BEHAVIOR         : c_int = 0; // TODO: Not yet exposed
-pub const RELEASE_BEHAVIOR_FLUSH       : c_int = 0x00035001; // TODO: Not yet exposed
-pub const RELEASE_BEHAVIOR_NONE        : c_int = 0x00035002; // TODO: Not yet exposed
+pub const ANY_RELEASE_BEHAVIOR         : c_int = 0;
+pub const RELEASE_BEHAVIOR_FLUSH       : c_int = 0x00035001;
+pub const RELEASE_BEHAVIOR_NONE        : c_int = 0x00035002;
 
-pub const ARROW_CURSOR                 : c_int = 0x00036001; // TODO: Not yet exposed
-pub const IBEAM_CURSOR                 : c_int = 0x00036002; // TODO: Not yet exposed
-pub const CROSSHAIR_CURSOR             : c_int = 0x00036003; // TODO: Not yet exposed
-pub const HAND_CURSOR                  : c_int = 0x00036004; // TODO: Not yet exposed
-pub const HRESIZE_CURSOR               : c_int = 0x00036005; // TODO: Not yet exposed
-pub const VRESIZE_CURSOR               : c_int = 0x00036006; // TODO: Not yet exposed
+pub const ARROW_CURSOR                 : c_int = 0x00036001;
+pub const IBEAM_CURSOR                 : c_int = 0x00036002;
+pub const CROSSHAIR_CURSOR             : c_int = 0x00036003;
+pub const HAND_CURSOR                  : c_int = 0x00036004;
+pub const HRESIZE_CURSOR               : c_int = 0x00036005;
+pub const VRESIZE_CURSOR               : c_int = 0x00036006;
 
 pub const CONNECTED                    : c_int = 0x00040001;
 pub const DISCONNECTED                 : c_int = 0x00040002;
 
-pub const DONT_CARE                    : c_int = -1; // TODO: Not yet exposed
+pub const DONT_CARE                    : c_int = -1;
 
 pub type GLFWglproc             = *const c_void;
 
@@ -278,16 +314,19 @@ pub type GLFWwindowclosefun     = extern "C" fn(*mut GLFWwindow);
 pub type GLFWwindowrefreshfun   = extern "C" fn(*mut GLFWwindow);
 pub type GLFWwindowfocusfun     = extern "C" fn(*mut GLFWwindow, c_int);
 pub type GLFWwindowiconifyfun   = extern "C" fn(*mut GLFWwindow, c_int);
+pub type GLFWwindowmaximizefun  = extern "C" fn(*mut GLFWwindow, c_int);
 pub type GLFWframebuffersizefun = extern "C" fn(*mut GLFWwindow, c_int, c_int);
+pub type GLFWwindowcontentscalefun = extern "C" fn(*mut GLFWwindow, c_float, c_float);
 pub type GLFWmousebuttonfun     = extern "C" fn(*mut GLFWwindow, c_int, c_int, c_int);
 pub type GLFWcursorposfun       = extern "C" fn(*mut GLFWwindow, c_double, c_double);
 pub type GLFWcursorenterfun     = extern "C" fn(*mut GLFWwindow, c_int);
 pub type GLFWscrollfun          = extern "C" fn(*mut GLFWwindow, c_double, c_double);
 pub type GLFWkeyfun             = extern "C" fn(*mut GLFWwindow, c_int, c_int, c_int, c_int);
 pub type GLFWcharfun            = extern "C" fn(*mut GLFWwindow, c_uint);
-pub type GLFWcharmodsfun        = extern "C" fn(*mut GLFWwindow, c_uint, c_int); // TODO: Not yet exposed
-pub type GLFWdropfun            = extern "C" fn(*mut GLFWwindow, c_int, *mut *const c_char); // TODO: Not yet exposed
+pub type GLFWcharmodsfun        = extern "C" fn(*mut GLFWwindow, c_uint, c_int);
+pub type GLFWdropfun            = extern "C" fn(*mut GLFWwindow, c_int, *mut *const c_char);
 pub type GLFWmonitorfun         = extern "C" fn(*mut GLFWmonitor, c_int);
+pub type GLFWjoystickfun        = extern "C" fn(c_int, c_int);
 
 #[allow(missing_copy_implementations)]
 pub enum GLFWmonitor {}
@@ -327,6 +366,13 @@ pub struct GLFWimage {
     pub pixels: *mut c_uchar,
 }
 
+#[allow(missing_copy_implementations)]
+#[repr(C)]
+pub struct GLFWgamepadstate {
+    pub buttons: [c_uchar; 15],
+    pub axes:    [c_float; 6],
+}
+
 // C function bindings
 
 extern "C" {
@@ -340,7 +386,9 @@ extern "C" {
     pub fn glfwGetMonitors(count: *mut c_int) -> *mut *mut GLFWmonitor;
     pub fn glfwGetPrimaryMonitor() -> *mut GLFWmonitor;
     pub fn glfwGetMonitorPos(monitor: *mut GLFWmonitor, xpos: *mut c_int, ypos: *mut c_int);
+    #[cfg(feature = "glfw-3-3")] pub fn glfwGetMonitorWorkarea(monitor: *mut GLFWmonitor, xpos: *mut c_int, ypos: *mut c_int, width: *mut c_int, height: *mut c_int);
     pub fn glfwGetMonitorPhysicalSize(monitor: *mut GLFWmonitor, width: *mut c_int, height: *mut c_int);
+    #[cfg(feature = "glfw-3-3")] pub fn glfwGetMonitorContentScale(monitor: *mut GLFWmonitor, xscale: *mut c_float, yscale: *mut c_float);
     pub fn glfwGetMonitorName(monitor: *mut GLFWmonitor) -> *const c_char;
     pub fn glfwSetMonitorCallback(cbfun: Option<GLFWmonitorfun>) -> Option<GLFWmonitorfun>;
     pub fn glfwGetVideoModes(monitor: *mut GLFWmonitor, count: *mut c_int) -> *const GLFWvidmode;
@@ -356,16 +404,22 @@ extern "C" {
     pub fn glfwWindowShouldClose(window: *mut GLFWwindow) -> c_int;
     pub fn glfwSetWindowShouldClose(window: *mut GLFWwindow, value: c_int);
     pub fn glfwSetWindowTitle(window: *mut GLFWwindow, title: *const c_char);
+    #[cfg(feature = "glfw-3-2")] pub fn glfwSetWindowIcon(window: *mut GLFWwindow, count: c_int, images: *const GLFWimage);
     pub fn glfwGetWindowPos(window: *mut GLFWwindow, xpos: *mut c_int, ypos: *mut c_int);
     pub fn glfwSetWindowPos(window: *mut GLFWwindow, xpos: c_int, ypos: c_int);
     pub fn glfwGetWindowSize(window: *mut GLFWwindow, width: *mut c_int, height: *mut c_int);
     pub fn glfwSetWindowSize(window: *mut GLFWwindow, width: c_int, height: c_int);
+    #[cfg(feature = "glfw-3-2")] pub fn glfwSetWindowSizeLimits(window: *mut GLFWwindow, minwidth: c_int, minheight: c_int, maxwidth: c_int, maxheight: c_int);
+    #[cfg(feature = "glfw-3-2")] pub fn glfwSetWindowAspectRatio(window: *mut GLFWwindow, numer: c_int, denom: c_int);
     pub fn glfwGetFramebufferSize(window: *mut GLFWwindow, width: *mut c_int, height: *mut c_int);
+    #[cfg(feature = "glfw-3-3")] pub fn glfwGetWindowContentScale(window: *mut GLFWwindow, xscale: *mut c_float, yscale: *mut c_float);
     pub fn glfwIconifyWindow(window: *mut GLFWwindow);
+    #[cfg(feature = "glfw-3-2")] pub fn glfwMaximizeWindow(window: *mut GLFWwindow);
     pub fn glfwRestoreWindow(window: *mut GLFWwindow);
     pub fn glfwShowWindow(window: *mut GLFWwindow);
     pub fn glfwHideWindow(window: *mut GLFWwindow);
     pub fn glfwGetWindowMonitor(window: *mut GLFWwindow) -> *mut GLFWmonitor;
+    #[cfg(feature = "glfw-3-2")] pub fn glfwSetWindowMonitor(window: *mut GLFWwindow, monitor: *mut GLFWmonitor, xpos: c_int, ypos: c_int, width: c_int, height: c_int, refreshRate: c_int);
     pub fn glfwGetWindowAttrib(window: *mut GLFWwindow, attrib: c_int) -> c_int;
     pub fn glfwSetWindowUserPointer(window: *mut GLFWwindow, pointer: *mut c_void);
     pub fn glfwGetWindowUserPointer(window: *mut GLFWwindow) -> *mut c_void;
@@ -375,11 +429,13 @@ extern "C" {
     pub fn glfwSetWindowRefreshCallback(window: *mut GLFWwindow, cbfun: Option<GLFWwindowrefreshfun>) -> Option<GLFWwindowrefreshfun>;
     pub fn glfwSetWindowFocusCallback(window: *mut GLFWwindow, cbfun: Option<GLFWwindowfocusfun>) -> Option<GLFWwindowfocusfun>;
     pub fn glfwSetWindowIconifyCallback(window: *mut GLFWwindow, cbfun: Option<GLFWwindowiconifyfun>) -> Option<GLFWwindowiconifyfun>;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwSetWindowMaximizeCallback(window: *mut GLFWwindow, cbfun: Option<GLFWwindowmaximizefun>) -> Option<GLFWwindowmaximizefun>;
     pub fn glfwSetFramebufferSizeCallback(window: *mut GLFWwindow, cbfun: Option<GLFWframebuffersizefun>) -> Option<GLFWframebuffersizefun>;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwSetWindowContentScaleCallback(window: *mut GLFWwindow, cbfun: Option<GLFWwindowcontentscalefun>) -> Option<GLFWwindowcontentscalefun>;
 
     pub fn glfwPollEvents();
     pub fn glfwWaitEvents();
-    pub fn glfwPostEmptyEvent(); // TODO: Not yet exposed
+    pub fn glfwPostEmptyEvent();
 
     pub fn glfwGetInputMode(window: *mut GLFWwindow, mode: c_int) -> c_int;
     pub fn glfwSetInputMode(window: *mut GLFWwindow, mode: c_int, value: c_int);
@@ -387,23 +443,29 @@ extern "C" {
     pub fn glfwGetMouseButton(window: *mut GLFWwindow, button: c_int) -> c_int;
     pub fn glfwGetCursorPos(window: *mut GLFWwindow, xpos: *mut c_double, ypos: *mut c_double);
     pub fn glfwSetCursorPos(window: *mut GLFWwindow, xpos: c_double, ypos: c_double);
-    pub fn glfwCreateCursor(image: *const GLFWimage, xhot: c_int, yhot: c_int) -> *mut GLFWcursor; // TODO: Not yet exposed
-    pub fn glfwCreateStandardCursor(shape: c_int) -> *mut GLFWcursor; // TODO: Not yet exposed
-    pub fn glfwDestroyCursor(cursor: *mut GLFWcursor); // TODO: Not yet exposed
-    pub fn glfwSetCursor(window: *mut GLFWwindow, cursor: *mut GLFWcursor); // TODO: Not yet exposed
+    pub fn glfwCreateCursor(image: *const GLFWimage, xhot: c_int, yhot: c_int) -> *mut GLFWcursor;
+    pub fn glfwCreateStandardCursor(shape: c_int) -> *mut GLFWcursor;
+    pub fn glfwDestroyCursor(cursor: *mut GLFWcursor);
+    pub fn glfwSetCursor(window: *mut GLFWwindow, cursor: *mut GLFWcursor);
     pub fn glfwSetKeyCallback(window: *mut GLFWwindow, cbfun: Option<GLFWkeyfun>) -> Option<GLFWkeyfun>;
     pub fn glfwSetCharCallback(window: *mut GLFWwindow, cbfun: Option<GLFWcharfun>) -> Option<GLFWcharfun>;
-    pub fn glfwSetCharModsCallback(window: *mut GLFWwindow, cbfun: Option<GLFWcharmodsfun>) -> Option<GLFWcharmodsfun>; // TODO: Not yet exposed
+    pub fn glfwSetCharModsCallback(window: *mut GLFWwindow, cbfun: Option<GLFWcharmodsfun>) -> Option<GLFWcharmodsfun>;
     pub fn glfwSetMouseButtonCallback(window: *mut GLFWwindow, cbfun: Option<GLFWmousebuttonfun>) -> Option<GLFWmousebuttonfun>;
     pub fn glfwSetCursorPosCallback(window: *mut GLFWwindow, cbfun: Option<GLFWcursorposfun>) -> Option<GLFWcursorposfun>;
     pub fn glfwSetCursorEnterCallback(window: *mut GLFWwindow, cbfun: Option<GLFWcursorenterfun>) -> Option<GLFWcursorenterfun>;
     pub fn glfwSetScrollCallback(window: *mut GLFWwindow, cbfun: Option<GLFWscrollfun>) -> Option<GLFWscrollfun>;
-    pub fn glfwSetDropCallback(window: *mut GLFWwindow, cbfun: Option<GLFWdropfun>) -> Option<GLFWdropfun>; // TODO: Not yet exposed
+    pub fn glfwSetDropCallback(window: *mut GLFWwindow, cbfun: Option<GLFWdropfun>) -> Option<GLFWdropfun>;
 
     pub fn glfwJoystickPresent(joy: c_int) -> c_int;
     pub fn glfwGetJoystickAxes(joy: c_int, count: *mut c_int) -> *const c_float;
     pub fn glfwGetJoystickButtons(joy: c_int, count: *mut c_int) -> *const c_uchar;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwGetJoystickHats(joy: c_int, count: *mut c_int) -> *const c_uchar;
     pub fn glfwGetJoystickName(joy: c_int) -> *const c_char;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwGetJoystickGUID(joy: c_int) -> *const c_char;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwJoystickIsGamepad(joy: c_int) -> c_int;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwUpdateGamepadMappings(string: *const c_char) -> c_int;
+    #[cfg(feature = "glfw-3-3")] pub fn glfwGetGamepadState(joy: c_int, state: *mut GLFWgamepadstate) -> c_int;
+    #[cfg(feature = "glfw-3-2")] pub fn glfwSetJoystickCallback(cbfun: Option<GLFWjoystickfun>) -> Option<GLFWjoystickfun>;
 
     pub fn glfwSetClipboardString(window: *mut GLFWwindow, string: *const c_char);
     pub fn glfwGetClipboardString(window: *mut GLFWwindow) -> *const c_char;
@@ -0,0 +1,654 @@
+// Copyright 2013 The GLFW-RS Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Gamepad input through SDL_GameControllerDB mappings.
+//!
+//! With the `glfw-3-3` feature the mappings are handed to GLFW, which
+//! matches them against each joystick's GUID. Earlier versions of GLFW have
+//! no gamepad support, so the mappings are parsed and applied here instead,
+//! following the semantics of GLFW 3.3. As joystick GUIDs are not available
+//! before GLFW 3.3, a mapping must then be bound to a joystick explicitly
+//! with `Glfw::bind_gamepad_mapping`.
+
+use libc::c_int;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use super::{ffi, Action, Glfw, Joystick};
+
+/// Gamepad buttons, named after their position on an Xbox controller.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum GamepadButton {
+    A                           = ffi::GAMEPAD_BUTTON_A,
+    B                           = ffi::GAMEPAD_BUTTON_B,
+    X                           = ffi::GAMEPAD_BUTTON_X,
+    Y                           = ffi::GAMEPAD_BUTTON_Y,
+    LeftBumper                  = ffi::GAMEPAD_BUTTON_LEFT_BUMPER,
+    RightBumper                 = ffi::GAMEPAD_BUTTON_RIGHT_BUMPER,
+    Back                        = ffi::GAMEPAD_BUTTON_BACK,
+    Start                       = ffi::GAMEPAD_BUTTON_START,
+    Guide                       = ffi::GAMEPAD_BUTTON_GUIDE,
+    LeftThumb                   = ffi::GAMEPAD_BUTTON_LEFT_THUMB,
+    RightThumb                  = ffi::GAMEPAD_BUTTON_RIGHT_THUMB,
+    DpadUp                      = ffi::GAMEPAD_BUTTON_DPAD_UP,
+    DpadRight                   = ffi::GAMEPAD_BUTTON_DPAD_RIGHT,
+    DpadDown                    = ffi::GAMEPAD_BUTTON_DPAD_DOWN,
+    DpadLeft                    = ffi::GAMEPAD_BUTTON_DPAD_LEFT,
+}
+
+/// Gamepad axes. The sticks range from `-1.0` to `1.0`, and the triggers
+/// from `-1.0` when released to `1.0` when fully pressed.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum GamepadAxis {
+    LeftX                       = ffi::GAMEPAD_AXIS_LEFT_X,
+    LeftY                       = ffi::GAMEPAD_AXIS_LEFT_Y,
+    RightX                      = ffi::GAMEPAD_AXIS_RIGHT_X,
+    RightY                      = ffi::GAMEPAD_AXIS_RIGHT_Y,
+    LeftTrigger                 = ffi::GAMEPAD_AXIS_LEFT_TRIGGER,
+    RightTrigger                = ffi::GAMEPAD_AXIS_RIGHT_TRIGGER,
+}
+
+const BUTTON_COUNT: usize = ffi::GAMEPAD_BUTTON_LAST as usize + 1;
+const AXIS_COUNT: usize = ffi::GAMEPAD_AXIS_LAST as usize + 1;
+
+/// The state of a gamepad, as returned by `Joystick::get_gamepad_state`.
+#[derive(Copy, Clone, Debug)]
+pub struct GamepadState {
+    buttons: [Action; BUTTON_COUNT],
+    axes: [f32; AXIS_COUNT],
+}
+
+impl GamepadState {
+    /// Returns the state of the given button, which is either
+    /// `Action::Press` or `Action::Release`.
+    pub fn get_button_state(&self, button: GamepadButton) -> Action {
+        self.buttons[button as usize]
+    }
+
+    /// Returns the value of the given axis.
+    pub fn get_axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+}
+
+/// An error that might be returned when updating the gamepad mappings.
+#[derive(Debug)]
+pub enum MappingError {
+    /// The mappings file could not be read.
+    Io(io::Error),
+    /// The mapping on the given line, counting from 1, is invalid.
+    Invalid(usize, &'static str),
+    /// GLFW refused the mappings.
+    Rejected,
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MappingError::Io(ref err) => write!(f, "Failed to read gamepad mappings: {}", err),
+            MappingError::Invalid(line, reason) => write!(f, "Invalid gamepad mapping on line {}: {}", line, reason),
+            MappingError::Rejected => write!(f, "GLFW rejected the gamepad mappings"),
+        }
+    }
+}
+
+/// The joystick input a gamepad button or axis is mapped to.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Element {
+    Unmapped,
+    Axis { index: usize, scale: f32, offset: f32 },
+    Button(usize),
+    HatBit { hat: usize, bit: usize },
+}
+
+#[cfg_attr(feature = "glfw-3-3", allow(dead_code))]
+struct Mapping {
+    guid: String,
+    buttons: [Element; BUTTON_COUNT],
+    axes: [Element; AXIS_COUNT],
+}
+
+enum Target {
+    Button(GamepadButton),
+    Axis(GamepadAxis),
+}
+
+#[cfg(target_os = "windows")]
+const PLATFORM: &'static str = "Windows";
+#[cfg(target_os = "macos")]
+const PLATFORM: &'static str = "Mac OS X";
+#[cfg(target_os = "linux")]
+const PLATFORM: &'static str = "Linux";
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+const PLATFORM: &'static str = "";
+
+fn target_from_key(key: &str) -> Option<Target> {
+    match key {
+        "a"             => Some(Target::Button(GamepadButton::A)),
+        "b"             => Some(Target::Button(GamepadButton::B)),
+        "x"             => Some(Target::Button(GamepadButton::X)),
+        "y"             => Some(Target::Button(GamepadButton::Y)),
+        "back"          => Some(Target::Button(GamepadButton::Back)),
+        "start"         => Some(Target::Button(GamepadButton::Start)),
+        "guide"         => Some(Target::Button(GamepadButton::Guide)),
+        "leftshoulder"  => Some(Target::Button(GamepadButton::LeftBumper)),
+        "rightshoulder" => Some(Target::Button(GamepadButton::RightBumper)),
+        "leftstick"     => Some(Target::Button(GamepadButton::LeftThumb)),
+        "rightstick"    => Some(Target::Button(GamepadButton::RightThumb)),
+        "dpup"          => Some(Target::Button(GamepadButton::DpadUp)),
+        "dpright"       => Some(Target::Button(GamepadButton::DpadRight)),
+        "dpdown"        => Some(Target::Button(GamepadButton::DpadDown)),
+        "dpleft"        => Some(Target::Button(GamepadButton::DpadLeft)),
+        "lefttrigger"   => Some(Target::Axis(GamepadAxis::LeftTrigger)),
+        "righttrigger"  => Some(Target::Axis(GamepadAxis::RightTrigger)),
+        "leftx"         => Some(Target::Axis(GamepadAxis::LeftX)),
+        "lefty"         => Some(Target::Axis(GamepadAxis::LeftY)),
+        "rightx"        => Some(Target::Axis(GamepadAxis::RightX)),
+        "righty"        => Some(Target::Axis(GamepadAxis::RightY)),
+        _               => None,
+    }
+}
+
+/// Splits the leading decimal number off `s`.
+fn split_index(s: &str) -> Result<(usize, &str), &'static str> {
+    let end = s.find(|c: char| !c.is_digit(10)).unwrap_or(s.len());
+    match s[..end].parse() {
+        Ok(index) => Ok((index, &s[end..])),
+        Err(_) => Err("missing input index"),
+    }
+}
+
+/// Parses an element such as `b0`, `h0.4`, `a2` or `+a3~`.
+fn parse_element(value: &str) -> Result<Element, &'static str> {
+    // A `+` or `-` prefix maps only the positive or negative half of an axis.
+    let (minimum, maximum, value) = if value.starts_with("+") {
+        (0.0, 1.0, &value[1..])
+    } else if value.starts_with("-") {
+        (-1.0, 0.0, &value[1..])
+    } else {
+        (-1.0, 1.0, value)
+    };
+    if value.starts_with("a") {
+        let (index, rest) = try!(split_index(&value[1..]));
+        let mut scale = 2.0 / (maximum - minimum);
+        let mut offset = -(maximum + minimum);
+        // A `~` suffix inverts the axis.
+        if rest.starts_with("~") {
+            scale = -scale;
+            offset = -offset;
+        }
+        Ok(Element::Axis { index: index, scale: scale, offset: offset })
+    } else if value.starts_with("b") {
+        let (index, _) = try!(split_index(&value[1..]));
+        Ok(Element::Button(index))
+    } else if value.starts_with("h") {
+        let (hat, rest) = try!(split_index(&value[1..]));
+        if !rest.starts_with(".") {
+            return Err("missing hat bit");
+        }
+        let (bit, _) = try!(split_index(&rest[1..]));
+        Ok(Element::HatBit { hat: hat, bit: bit })
+    } else {
+        // GLFW ignores inputs it doesn't recognise.
+        Ok(Element::Unmapped)
+    }
+}
+
+/// Parses a single mapping, returning `None` if it is meant for another
+/// platform.
+fn parse_mapping(line: &str) -> Result<Option<Mapping>, &'static str> {
+    let mut fields = line.split(',');
+    let guid = fields.next().unwrap_or("");
+    if guid.len() != 32 || !guid.chars().all(|c| c.is_digit(16)) {
+        return Err("invalid GUID");
+    }
+    if fields.next().is_none() {
+        return Err("missing name");
+    }
+    let mut mapping = Mapping {
+        guid: guid.to_string(),
+        buttons: [Element::Unmapped; BUTTON_COUNT],
+        axes: [Element::Unmapped; AXIS_COUNT],
+    };
+    for field in fields {
+        if field.is_empty() {
+            continue;
+        }
+        let (key, value) = match field.find(':') {
+            Some(i) => (&field[..i], &field[i + 1..]),
+            None => return Err("missing ':' in element"),
+        };
+        if key == "platform" {
+            if value != PLATFORM {
+                return Ok(None);
+            }
+            continue;
+        }
+        match target_from_key(key) {
+            Some(Target::Button(button)) => mapping.buttons[button as usize] = try!(parse_element(value)),
+            Some(Target::Axis(axis))     => mapping.axes[axis as usize] = try!(parse_element(value)),
+            None => {}
+        }
+    }
+    Ok(Some(mapping))
+}
+
+/// Parses a set of mappings, one per line. Empty lines and comments starting
+/// with `#` are skipped. Fails on the first invalid mapping, so that either
+/// all of the mappings are applied or none of them are.
+fn parse_mappings(text: &str) -> Result<Vec<Mapping>, MappingError> {
+    let mut mappings = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("#") {
+            continue;
+        }
+        match parse_mapping(line) {
+            Ok(Some(mapping)) => mappings.push(mapping),
+            Ok(None) => {}
+            Err(reason) => return Err(MappingError::Invalid(i + 1, reason)),
+        }
+    }
+    Ok(mappings)
+}
+
+#[cfg(feature = "glfw-3-3")]
+fn apply_mappings(text: &str, _: Vec<Mapping>) -> Result<(), MappingError> {
+    match ::with_c_str(text, |text| unsafe { ffi::glfwUpdateGamepadMappings(text) }) {
+        ffi::TRUE => Ok(()),
+        _ => Err(MappingError::Rejected),
+    }
+}
+
+#[cfg(not(feature = "glfw-3-3"))]
+thread_local!(static MAPPINGS_KEY: ::std::cell::RefCell<Vec<Mapping>> = ::std::cell::RefCell::new(Vec::new()));
+
+/// The GUID of the mapping bound to each joystick, along with the number of
+/// hats the joystick has.
+#[cfg(not(feature = "glfw-3-3"))]
+thread_local!(static BINDINGS_KEY: ::std::cell::RefCell<Vec<(::JoystickId, String, usize)>> = ::std::cell::RefCell::new(Vec::new()));
+
+/// Decodes the hats at the end of the raw joystick input reported by GLFW
+/// versions before 3.3 into `ffi::HAT_*` bits, or returns `None` if there is
+/// too little input for the given number of hats. On Linux each
+/// hat is reported as a pair of axes after the other axes, and elsewhere as
+/// four buttons, for up, right, down and left, after the other buttons.
+#[cfg_attr(feature = "glfw-3-3", allow(dead_code))]
+fn decode_hats(axes: &[f32], buttons: &[c_int], hat_count: usize) -> Option<Vec<c_int>> {
+    if cfg!(target_os = "linux") {
+        if axes.len() < 2 * hat_count {
+            return None;
+        }
+        let base = axes.len() - 2 * hat_count;
+        Some((0..hat_count).map(|hat| {
+            let (x, y) = (axes[base + 2 * hat], axes[base + 2 * hat + 1]);
+            let mut bits = ffi::HAT_CENTERED;
+            if y < -0.5 { bits |= ffi::HAT_UP; }
+            if x > 0.5 { bits |= ffi::HAT_RIGHT; }
+            if y > 0.5 { bits |= ffi::HAT_DOWN; }
+            if x < -0.5 { bits |= ffi::HAT_LEFT; }
+            bits
+        }).collect())
+    } else {
+        if buttons.len() < 4 * hat_count {
+            return None;
+        }
+        let base = buttons.len() - 4 * hat_count;
+        Some((0..hat_count).map(|hat| {
+            let pressed = |i: usize| buttons[base + 4 * hat + i] == ffi::PRESS;
+            let mut bits = ffi::HAT_CENTERED;
+            if pressed(0) { bits |= ffi::HAT_UP; }
+            if pressed(1) { bits |= ffi::HAT_RIGHT; }
+            if pressed(2) { bits |= ffi::HAT_DOWN; }
+            if pressed(3) { bits |= ffi::HAT_LEFT; }
+            bits
+        }).collect())
+    }
+}
+
+#[cfg(not(feature = "glfw-3-3"))]
+fn apply_mappings(_: &str, mappings: Vec<Mapping>) -> Result<(), MappingError> {
+    MAPPINGS_KEY.with(|existing| {
+        let mut existing = existing.borrow_mut();
+        for mapping in mappings.into_iter() {
+            // As in GLFW, a mapping replaces any previous one for the same
+            // GUID.
+            match existing.iter().position(|m| m.guid == mapping.guid) {
+                Some(i) => existing[i] = mapping,
+                None => existing.push(mapping),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg_attr(feature = "glfw-3-3", allow(dead_code))]
+impl Mapping {
+    /// Checks that every input the mapping refers to exists on a joystick
+    /// with the given number of axes, buttons and hats.
+    fn is_valid_for(&self, axis_count: usize, button_count: usize, hat_count: usize) -> bool {
+        self.buttons.iter().chain(self.axes.iter()).all(|e| {
+            match *e {
+                Element::Axis { index, .. } => index < axis_count,
+                Element::Button(index) => index < button_count,
+                Element::HatBit { hat, .. } => hat < hat_count,
+                Element::Unmapped => true,
+            }
+        })
+    }
+
+    /// Computes the gamepad state from the raw joystick input, in the same
+    /// way as `glfwGetGamepadState`. Each hat is given as a combination of
+    /// the `ffi::HAT_*` bits.
+    fn evaluate(&self, axes: &[f32], buttons: &[c_int], hats: &[c_int]) -> GamepadState {
+        let mut state = GamepadState {
+            buttons: [Action::Release; BUTTON_COUNT],
+            axes: [0.0; AXIS_COUNT],
+        };
+        for (button, e) in state.buttons.iter_mut().zip(self.buttons.iter()) {
+            let pressed = match *e {
+                Element::Axis { index, scale, offset } => {
+                    let value = axes[index] * scale + offset;
+                    if offset < 0.0 || (offset == 0.0 && scale > 0.0) {
+                        value >= 0.0
+                    } else {
+                        value <= 0.0
+                    }
+                }
+                Element::Button(index) => buttons[index] == ffi::PRESS,
+                Element::HatBit { hat, bit } => hats[hat] & bit as c_int != 0,
+                Element::Unmapped => false,
+            };
+            if pressed {
+                *button = Action::Press;
+            }
+        }
+        for (axis, e) in state.axes.iter_mut().zip(self.axes.iter()) {
+            *axis = match *e {
+                Element::Axis { index, scale, offset } => (axes[index] * scale + offset).max(-1.0).min(1.0),
+                Element::Button(index) => if buttons[index] == ffi::PRESS { 1.0 } else { -1.0 },
+                Element::HatBit { hat, bit } => if hats[hat] & bit as c_int != 0 { 1.0 } else { -1.0 },
+                Element::Unmapped => 0.0,
+            };
+        }
+        state
+    }
+}
+
+impl Glfw {
+    /// Adds the given SDL_GameControllerDB mappings, one per line, replacing
+    /// any previous mappings for the same GUIDs. Mappings for other platforms
+    /// are skipped. The mappings are checked before any of them are added,
+    /// so if one is invalid, an error naming its line is returned and none
+    /// of them are added.
+    ///
+    /// Wrapper for `glfwUpdateGamepadMappings` when built with the
+    /// `glfw-3-3` feature.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// glfw.update_gamepad_mappings("030000005e0400008e02000014010000,Xbox 360 Controller,\
+    ///     a:b0,b:b1,x:b2,y:b3,leftx:a0,lefty:a1,platform:Linux,").unwrap();
+    /// ~~~
+    pub fn update_gamepad_mappings(&mut self, mappings: &str) -> Result<(), MappingError> {
+        let parsed = try!(parse_mappings(mappings));
+        apply_mappings(mappings, parsed)
+    }
+
+    /// Adds the SDL_GameControllerDB mappings contained in the given file,
+    /// such as a copy of `gamecontrollerdb.txt`. See
+    /// `update_gamepad_mappings`.
+    pub fn update_gamepad_mappings_from_file(&mut self, path: &Path) -> Result<(), MappingError> {
+        let mut file = try!(File::open(path).map_err(MappingError::Io));
+        let mut mappings = String::new();
+        try!(file.read_to_string(&mut mappings).map_err(MappingError::Io));
+        self.update_gamepad_mappings(&mappings)
+    }
+
+    /// Binds the mapping with the given GUID to a joystick, or removes the
+    /// joystick's binding if `guid` is `None`. Returns `false` without
+    /// changing the binding if no mapping with the GUID has been added.
+    ///
+    /// GLFW versions before 3.3 report neither joystick GUIDs nor hats, so
+    /// the mapping to use and the number of hats on the joystick must be
+    /// supplied here. The hats are read from the last `2 * hat_count` axes on
+    /// Linux, and from the last `4 * hat_count` buttons on other platforms.
+    /// With the `glfw-3-3` feature, GLFW matches mappings to joysticks
+    /// itself, and this function is not available.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// glfw.update_gamepad_mappings_from_file(Path::new("gamecontrollerdb.txt")).unwrap();
+    /// // The user picked their controller from a list of the mappings.
+    /// glfw.bind_gamepad_mapping(glfw::JoystickId::Joystick1, Some("030000005e0400008e02000014010000"), 1);
+    /// ~~~
+    #[cfg(not(feature = "glfw-3-3"))]
+    pub fn bind_gamepad_mapping(&mut self, id: ::JoystickId, guid: Option<&str>, hat_count: usize) -> bool {
+        let guid = match guid {
+            Some(guid) => guid,
+            None => {
+                BINDINGS_KEY.with(|bindings| bindings.borrow_mut().retain(|&(i, _, _)| i != id));
+                return true;
+            }
+        };
+        let known = MAPPINGS_KEY.with(|mappings| {
+            mappings.borrow().iter().any(|m| m.guid == guid)
+        });
+        if known {
+            BINDINGS_KEY.with(|bindings| {
+                let mut bindings = bindings.borrow_mut();
+                bindings.retain(|&(i, _, _)| i != id);
+                bindings.push((id, guid.to_string(), hat_count));
+            });
+        }
+        known
+    }
+}
+
+impl Joystick {
+    /// Returns whether the joystick is present and has a gamepad mapping.
+    ///
+    /// Wrapper for `glfwJoystickIsGamepad` when built with the `glfw-3-3`
+    /// feature.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn is_gamepad(&self) -> bool {
+        unsafe { ffi::glfwJoystickIsGamepad(self.id as c_int) == ffi::TRUE }
+    }
+
+    /// Returns whether the joystick is present and has a gamepad mapping.
+    #[cfg(not(feature = "glfw-3-3"))]
+    pub fn is_gamepad(&self) -> bool {
+        self.get_gamepad_state().is_some()
+    }
+
+    /// Returns the state of the joystick as a gamepad, or `None` if it is
+    /// not present or has no gamepad mapping.
+    ///
+    /// Wrapper for `glfwGetGamepadState` when built with the `glfw-3-3`
+    /// feature.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_gamepad_state(&self) -> Option<GamepadState> {
+        let mut raw = ffi::GLFWgamepadstate {
+            buttons: [0; BUTTON_COUNT],
+            axes: [0.0; AXIS_COUNT],
+        };
+        if unsafe { ffi::glfwGetGamepadState(self.id as c_int, &mut raw) } != ffi::TRUE {
+            return None;
+        }
+        let mut state = GamepadState {
+            buttons: [Action::Release; BUTTON_COUNT],
+            axes: [0.0; AXIS_COUNT],
+        };
+        for (button, &raw_button) in state.buttons.iter_mut().zip(raw.buttons.iter()) {
+            if raw_button as c_int == ffi::PRESS {
+                *button = Action::Press;
+            }
+        }
+        for (axis, &raw_axis) in state.axes.iter_mut().zip(raw.axes.iter()) {
+            *axis = raw_axis as f32;
+        }
+        Some(state)
+    }
+
+    /// Returns the state of the joystick as a gamepad, or `None` if it is
+    /// not present, no mapping is bound to it with
+    /// `Glfw::bind_gamepad_mapping`, or the mapping refers to inputs the
+    /// joystick doesn't have.
+    #[cfg(not(feature = "glfw-3-3"))]
+    pub fn get_gamepad_state(&self) -> Option<GamepadState> {
+        if !self.is_present() {
+            return None;
+        }
+        let binding = BINDINGS_KEY.with(|bindings| {
+            bindings.borrow().iter()
+                .find(|&&(i, _, _)| i == self.id)
+                .map(|&(_, ref guid, hat_count)| (guid.clone(), hat_count))
+        });
+        let (guid, hat_count) = match binding {
+            Some(binding) => binding,
+            None => return None,
+        };
+        let axes = self.get_axes();
+        let buttons = self.get_buttons();
+        let hats = match decode_hats(&axes, &buttons, hat_count) {
+            Some(hats) => hats,
+            None => return None,
+        };
+        MAPPINGS_KEY.with(|mappings| {
+            mappings.borrow().iter()
+                .find(|m| m.guid == guid)
+                .and_then(|m| {
+                    if m.is_valid_for(axes.len(), buttons.len(), hats.len()) {
+                        Some(m.evaluate(&axes, &buttons, &hats))
+                    } else {
+                        None
+                    }
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_hats, parse_element, parse_mapping, parse_mappings, Element, GamepadAxis, GamepadButton,
+                MappingError, PLATFORM};
+    use ffi;
+    use Action;
+
+    const GUID: &'static str = "030000005e0400008e02000014010000";
+
+    #[test]
+    fn parse_element_full_axis() {
+        assert_eq!(parse_element("a2"), Ok(Element::Axis { index: 2, scale: 1.0, offset: 0.0 }));
+    }
+
+    #[test]
+    fn parse_element_positive_half_axis_inverted() {
+        assert_eq!(parse_element("+a3~"), Ok(Element::Axis { index: 3, scale: -2.0, offset: 1.0 }));
+    }
+
+    #[test]
+    fn parse_element_negative_half_axis() {
+        assert_eq!(parse_element("-a1"), Ok(Element::Axis { index: 1, scale: 2.0, offset: 1.0 }));
+    }
+
+    #[test]
+    fn parse_element_button_and_hat() {
+        assert_eq!(parse_element("b12"), Ok(Element::Button(12)));
+        assert_eq!(parse_element("h0.4"), Ok(Element::HatBit { hat: 0, bit: 4 }));
+    }
+
+    #[test]
+    fn parse_element_unknown_and_invalid() {
+        assert_eq!(parse_element("x3"), Ok(Element::Unmapped));
+        assert!(parse_element("a").is_err());
+        assert!(parse_element("h1").is_err());
+    }
+
+    #[test]
+    fn parse_mapping_platform_filtering() {
+        let other = format!("{},Pad,a:b0,platform:Nonexistent,", GUID);
+        assert!(parse_mapping(&other).unwrap().is_none());
+        let current = format!("{},Pad,a:b0,platform:{},", GUID, PLATFORM);
+        assert!(parse_mapping(&current).unwrap().is_some());
+        let any = format!("{},Pad,a:b0", GUID);
+        assert!(parse_mapping(&any).unwrap().is_some());
+    }
+
+    #[test]
+    fn parse_mapping_bad_guid() {
+        assert!(parse_mapping("0300,Pad,a:b0").is_err());
+        assert!(parse_mapping("z30000005e0400008e02000014010000,Pad,a:b0").is_err());
+        assert!(parse_mapping(GUID).is_err());
+    }
+
+    #[test]
+    fn parse_mappings_all_or_nothing() {
+        let text = format!("# comment\n\n{},Pad,a:b0\n{},Other,b:b1\n", GUID, GUID);
+        assert_eq!(parse_mappings(&text).unwrap().len(), 2);
+        let text = format!("{},Pad,a:b0\n# comment\n0300,Bad,a:b0\n{},Other,b:b1\n", GUID, GUID);
+        match parse_mappings(&text) {
+            Err(MappingError::Invalid(line, _)) => assert_eq!(line, 3),
+            _ => panic!("expected the invalid mapping on line 3 to be reported"),
+        }
+    }
+
+    #[test]
+    fn evaluate_mapping() {
+        let line = format!("{},Pad,a:b0,b:b1,leftshoulder:+a3,dpup:h0.1,dpleft:h0.8,\
+                            leftx:a0,lefty:a1~,lefttrigger:a2,righttrigger:+a3,rightx:b1,", GUID);
+        let mapping = parse_mapping(&line).unwrap().unwrap();
+        let axes = [0.5, 0.25, -1.0, 0.75];
+        let buttons = [ffi::PRESS, ffi::RELEASE];
+        let hats = [ffi::HAT_UP];
+
+        assert!(mapping.is_valid_for(4, 2, 1));
+        assert!(!mapping.is_valid_for(3, 2, 1));
+        assert!(!mapping.is_valid_for(4, 2, 0));
+
+        let state = mapping.evaluate(&axes, &buttons, &hats);
+        assert_eq!(state.get_button_state(GamepadButton::A), Action::Press);
+        assert_eq!(state.get_button_state(GamepadButton::B), Action::Release);
+        assert_eq!(state.get_button_state(GamepadButton::LeftBumper), Action::Press);
+        assert_eq!(state.get_button_state(GamepadButton::DpadUp), Action::Press);
+        assert_eq!(state.get_button_state(GamepadButton::DpadLeft), Action::Release);
+        assert_eq!(state.get_button_state(GamepadButton::Start), Action::Release);
+        assert_eq!(state.get_axis(GamepadAxis::LeftX), 0.5);
+        assert_eq!(state.get_axis(GamepadAxis::LeftY), -0.25);
+        assert_eq!(state.get_axis(GamepadAxis::LeftTrigger), -1.0);
+        assert_eq!(state.get_axis(GamepadAxis::RightTrigger), 0.5);
+        assert_eq!(state.get_axis(GamepadAxis::RightX), -1.0);
+        assert_eq!(state.get_axis(GamepadAxis::RightY), 0.0);
+    }
+
+    #[test]
+    fn decode_hats_from_trailing_input() {
+        if cfg!(target_os = "linux") {
+            let axes = [0.3, 1.0, -1.0];
+            assert_eq!(decode_hats(&axes, &[], 1), Some(vec![ffi::HAT_RIGHT | ffi::HAT_UP]));
+            assert_eq!(decode_hats(&axes[..1], &[], 1), None);
+        } else {
+            let buttons = [ffi::PRESS, ffi::RELEASE, ffi::RELEASE, ffi::PRESS, ffi::PRESS];
+            assert_eq!(decode_hats(&[], &buttons, 1), Some(vec![ffi::HAT_DOWN | ffi::HAT_LEFT]));
+            assert_eq!(decode_hats(&[], &buttons[..3], 1), None);
+        }
+    }
+}
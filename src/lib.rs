@@ -73,12 +73,15 @@ extern crate log;
 extern crate bitflags;
 
 use libc::{c_char, c_double, c_float, c_int};
-use libc::{c_ushort, c_void};
+use libc::{c_uchar, c_ushort, c_void};
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::mem;
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::mpsc::{channel, Receiver, SendError, Sender};
 use std::fmt;
-use std::marker::Send;
+use std::hash::{Hash, Hasher};
+use std::marker::{PhantomData, Send};
+use std::path::PathBuf;
 use std::ptr;
 use std::slice;
 use semver::Version;
@@ -90,8 +93,11 @@ pub use self::MouseButton::Button2 as MouseButtonRight;
 /// Alias to `MouseButton3`, supplied for improved clarity.
 pub use self::MouseButton::Button3 as MouseButtonMiddle;
 
+pub use self::gamepad::{GamepadAxis, GamepadButton, GamepadState, MappingError};
+
 pub mod ffi;
 mod callbacks;
+mod gamepad;
 
 /// Input actions.
 #[repr(i32)]
@@ -291,6 +297,20 @@ pub enum Error {
     FormatUnavailable           = ffi::FORMAT_UNAVAILABLE,
 }
 
+/// An error reported by GLFW through the error callback, consisting of the
+/// error token and the accompanying description.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GlfwError {
+    pub error: Error,
+    pub description: String,
+}
+
+impl fmt::Display for GlfwError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GLFW Error ({:?}): {}", self.error, self.description)
+    }
+}
+
 /// An error callback. This can be supplied with some user data to be passed to
 /// the callback function when it is triggered.
 pub type ErrorCallback<UserData> = Callback<fn(Error, String, &UserData), UserData>;
@@ -323,6 +343,43 @@ pub enum CursorMode {
     Disabled              = ffi::CURSOR_DISABLED,
 }
 
+/// Standard system cursor shapes.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum StandardCursor {
+    /// The regular arrow cursor.
+    Arrow                 = ffi::ARROW_CURSOR,
+    /// The text input I-beam cursor.
+    IBeam                 = ffi::IBEAM_CURSOR,
+    /// The crosshair cursor.
+    Crosshair             = ffi::CROSSHAIR_CURSOR,
+    /// The hand cursor.
+    Hand                  = ffi::HAND_CURSOR,
+    /// The horizontal resize arrow cursor.
+    HResize               = ffi::HRESIZE_CURSOR,
+    /// The vertical resize arrow cursor.
+    VResize               = ffi::VRESIZE_CURSOR,
+}
+
+/// A cursor image that can be attached to a window using
+/// `Window::set_cursor`. The cursor is destroyed when it is dropped.
+pub struct Cursor {
+    ptr: *mut ffi::GLFWcursor,
+}
+
+impl fmt::Debug for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cursor({:p})", self.ptr)
+    }
+}
+
+impl Drop for Cursor {
+    /// Wrapper for `glfwDestroyCursor`.
+    fn drop(&mut self) {
+        unsafe { ffi::glfwDestroyCursor(self.ptr); }
+    }
+}
+
 /// Describes a single video mode.
 #[derive(Copy)]
 pub struct VidMode {
@@ -335,12 +392,187 @@ pub struct VidMode {
 }
 
 /// Describes the gamma ramp of a monitor.
+///
+/// The `red`, `green` and `blue` channels must all have the same length. Some
+/// platforms only accept ramps of the same size as the monitor's current
+/// ramp, which can be retrieved with `Monitor::get_gamma_ramp`. On Windows
+/// this is always 256.
+#[derive(Clone, Debug)]
 pub struct GammaRamp {
     pub red:    Vec<c_ushort>,
     pub green:  Vec<c_ushort>,
     pub blue:   Vec<c_ushort>,
 }
 
+/// An error that might be returned when setting a gamma ramp.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GammaRampError {
+    /// The ramp contains no entries.
+    Empty,
+    /// The red, green and blue channels have different lengths.
+    MismatchedLengths,
+}
+
+impl fmt::Display for GammaRampError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GammaRampError: {:?}", *self)
+    }
+}
+
+impl GammaRamp {
+    /// Creates a ramp of `size` entries by evaluating `f` for inputs evenly
+    /// spaced between `0.0` and `1.0`. `f` returns the red, green and blue
+    /// outputs, which are clamped to the same range.
+    fn from_fn<F>(size: usize, f: F) -> GammaRamp where F: Fn(f32) -> (f32, f32, f32) {
+        fn to_entry(value: f32) -> c_ushort {
+            (value.max(0.0).min(1.0) * 65535.0 + 0.5) as c_ushort
+        }
+        let mut ramp = GammaRamp {
+            red:    Vec::with_capacity(size),
+            green:  Vec::with_capacity(size),
+            blue:   Vec::with_capacity(size),
+        };
+        for i in 0..size {
+            let input = if size > 1 { i as f32 / (size - 1) as f32 } else { 1.0 };
+            let (red, green, blue) = f(input);
+            ramp.red.push(to_entry(red));
+            ramp.green.push(to_entry(green));
+            ramp.blue.push(to_entry(blue));
+        }
+        ramp
+    }
+
+    /// Creates a linear ramp of `size` entries that leaves colours unchanged.
+    pub fn identity(size: usize) -> GammaRamp {
+        GammaRamp::from_fn(size, |x| (x, x, x))
+    }
+
+    /// Creates a ramp of `size` entries for the given gamma exponent, using
+    /// the same curve as `Monitor::set_gamma`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gamma` is not positive.
+    pub fn from_gamma(size: usize, gamma: f32) -> GammaRamp {
+        assert!(gamma > 0.0, "gamma must be positive");
+        GammaRamp::from_fn(size, |x| {
+            let value = x.powf(1.0 / gamma);
+            (value, value, value)
+        })
+    }
+
+    /// Creates a ramp of `size` entries that tints the display to approximate
+    /// the given colour temperature in Kelvin. `6500.0` leaves colours
+    /// roughly unchanged, while lower temperatures give a warmer image.
+    pub fn from_color_temperature(size: usize, kelvin: f32) -> GammaRamp {
+        // Approximation of the blackbody colour by Tanner Helland, normalised
+        // so that the brightest channel is 1.0.
+        let temp = kelvin.max(1000.0).min(40000.0) / 100.0;
+        let red = if temp <= 66.0 {
+            255.0
+        } else {
+            329.698727446 * (temp - 60.0).powf(-0.1332047592)
+        };
+        let green = if temp <= 66.0 {
+            99.4708025861 * temp.ln() - 161.1195681661
+        } else {
+            288.1221695283 * (temp - 60.0).powf(-0.0755148492)
+        };
+        let blue = if temp >= 66.0 {
+            255.0
+        } else if temp <= 19.0 {
+            0.0
+        } else {
+            138.5177312231 * (temp - 10.0).ln() - 305.0447927307
+        };
+        let clamp = |c: f32| c.max(0.0).min(255.0) / 255.0;
+        let (red, green, blue) = (clamp(red), clamp(green), clamp(blue));
+        GammaRamp::from_fn(size, |x| (x * red, x * green, x * blue))
+    }
+
+    /// Creates a ramp of `size` entries with the given brightness and
+    /// contrast. A `brightness` of `0.0` and a `contrast` of `1.0` leave
+    /// colours unchanged. `brightness` is added to each output, and
+    /// `contrast` scales the outputs around the midpoint.
+    pub fn from_brightness_contrast(size: usize, brightness: f32, contrast: f32) -> GammaRamp {
+        GammaRamp::from_fn(size, |x| {
+            let value = (x - 0.5) * contrast + 0.5 + brightness;
+            (value, value, value)
+        })
+    }
+
+    /// Checks that the ramp can be passed to GLFW.
+    fn validate(&self) -> Result<(), GammaRampError> {
+        if self.red.len() != self.green.len() || self.red.len() != self.blue.len() {
+            Err(GammaRampError::MismatchedLengths)
+        } else if self.red.is_empty() {
+            Err(GammaRampError::Empty)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A gamma ramp saved by `Monitor::save_gamma_ramp`, which is restored to the
+/// monitor when this is dropped, including when unwinding from a panic. The
+/// ramp is not restored if the monitor has been disconnected in the meantime.
+///
+/// Like `MonitorHandle`, this cannot be sent to other threads, so that it is
+/// always dropped on the main thread where the ramp can be restored.
+pub struct SavedGammaRamp {
+    monitor: MonitorHandle,
+    ramp: GammaRamp,
+}
+
+impl SavedGammaRamp {
+    /// Returns the monitor the ramp was saved from.
+    pub fn monitor(&self) -> MonitorHandle {
+        self.monitor
+    }
+
+    /// Returns the saved ramp.
+    pub fn ramp(&self) -> &GammaRamp {
+        &self.ramp
+    }
+}
+
+impl Drop for SavedGammaRamp {
+    fn drop(&mut self) {
+        let ramp = &self.ramp;
+        match self.monitor.with_monitor(|m| m.set_gamma_ramp(ramp)) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to restore the saved gamma ramp: {}", e),
+            Err(e) => debug!("Not restoring the saved gamma ramp: {}", e),
+        }
+    }
+}
+
+/// An image made up of 32-bit RGBA pixels, with 8 bits per channel. The
+/// pixels are arranged canonically as sequential rows, starting from the
+/// top-left corner.
+#[derive(Clone, Debug)]
+pub struct PixelImage {
+    pub width:  u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl PixelImage {
+    /// Returns a `GLFWimage` that borrows the pixels of this image. The result
+    /// must not outlive `self`.
+    fn to_glfw_image(&self) -> ffi::GLFWimage {
+        assert!(self.pixels.len() == self.width as usize * self.height as usize * 4,
+                "PixelImage of {} x {} pixels must contain {} bytes, found {}",
+                self.width, self.height, self.width as usize * self.height as usize * 4,
+                self.pixels.len());
+        ffi::GLFWimage {
+            width:  self.width as c_int,
+            height: self.height as c_int,
+            pixels: self.pixels.as_ptr() as *mut c_uchar,
+        }
+    }
+}
+
 /// An OpenGL process address.
 pub type GLProc = ffi::GLFWglproc;
 
@@ -391,12 +623,64 @@ impl fmt::Display for InitError {
 ///
 /// # Returns
 ///
-/// - If initialization was successful a `Glfw` token will be returned along
-///   with a `Receiver` from which errors can be intercepted.
+/// - If initialization was successful a `Glfw` token will be returned.
 /// - Subsequent calls to `init` will return `Err(AlreadyInitialized)`.
 /// - If an initialization error occured within the GLFW library
 ///   `Err(InternalInitError)` will be returned.
-pub fn init<UserData: 'static>(mut callback: Option<ErrorCallback<UserData>>) -> Result<Glfw, InitError> {
+pub fn init<UserData: Send + 'static>(callback: Option<ErrorCallback<UserData>>) -> Result<Glfw, InitError> {
+    init_intern(callback.map(boxed_error_callback))
+}
+
+/// Initializes the GLFW library, sending each error that is reported into the
+/// returned `Receiver` instead of passing it to an error callback. This must be
+/// called on the main platform thread.
+///
+/// Wrapper for `glfwInit`.
+///
+/// # Example
+///
+/// ~~~no_run
+/// extern crate glfw;
+///
+/// fn main() {
+///     let (mut glfw, errors) = glfw::init_with_error_receiver().unwrap();
+///
+///     loop {
+///         glfw.poll_events();
+///         for (error, description) in glfw::flush_messages(&errors) {
+///             println!("GLFW error {:?}: {}", error, description);
+///         }
+///     }
+/// }
+/// ~~~
+///
+/// # Returns
+///
+/// - If initialization was successful a `Glfw` token will be returned along
+///   with a `Receiver` from which errors can be intercepted.
+/// - Subsequent calls to `init` or `init_with_error_receiver` will return
+///   `Err(AlreadyInitialized)`.
+/// - If an initialization error occured within the GLFW library
+///   `Err(InternalInitError)` will be returned. Any errors reported during
+///   initialization are lost in this case.
+pub fn init_with_error_receiver() -> Result<(Glfw, Receiver<(Error, String)>), InitError> {
+    let (sender, receiver) = channel();
+    let callback: Box<FnMut(Error, String) + Send> = Box::new(move |error, description| {
+        let _ = sender.send((error, description));
+    });
+    init_intern(Some(callback)).map(|glfw| (glfw, receiver))
+}
+
+/// Converts an `ErrorCallback` into the boxed closure stored by the error
+/// callback machinery.
+fn boxed_error_callback<UserData: Send + 'static>(callback: ErrorCallback<UserData>) -> Box<FnMut(Error, String) + Send> {
+    Box::new(move |error, description| {
+        (callback.f)(error, description, &callback.data)
+    })
+}
+
+/// Initializes the GLFW library with an already boxed error callback.
+fn init_intern(mut callback: Option<Box<FnMut(Error, String) + Send>>) -> Result<Glfw, InitError> {
     use std::sync::{Once, ONCE_INIT};
     static mut INIT: Once = ONCE_INIT;
     let mut result = Err(InitError::AlreadyInitialized);
@@ -411,6 +695,9 @@ pub fn init<UserData: 'static>(mut callback: Option<ErrorCallback<UserData>>) ->
             }
             if ffi::glfwInit() == ffi::TRUE {
                 result = Ok(());
+                // The monitor callback tracks disconnections in order to
+                // invalidate `MonitorHandle`s, so it is always installed.
+                callbacks::monitor::install();
                 std::rt::at_exit(|| {
                     ffi::glfwTerminate()
                 });
@@ -425,36 +712,29 @@ pub fn init<UserData: 'static>(mut callback: Option<ErrorCallback<UserData>>) ->
 impl Glfw {
     /// Sets the error callback, overwriting the previous one stored.
     ///
+    /// The callback is shared by all threads, so it is also called for errors
+    /// raised on other threads, for example by `Context::swap_buffers` on a
+    /// `RenderContext`. It is called on the thread that raised the error, and
+    /// must not itself call GLFW functions that could raise errors.
+    ///
     /// # Example
     ///
     /// ~~~ignore
-    /// use std::cell::Cell;
-    ///
-    /// fn error_callback(_: glfw::Error, description: String, error_count: &Cell<usize>) {
-    ///     println!("GLFW error {}: {}", error_count.get(), description);
-    ///     error_count.set(error_count.get() + 1);
-    /// }
+    /// let mut error_count = 0;
     ///
     /// // sets a new callback
-    /// glfw.set_error_callback(Some(
-    ///     glfw::Callback {
-    ///         f: error_callback,
-    ///         data: Cell::new(0),
-    ///     }
-    /// ));
+    /// glfw.set_error_callback(Some(Box::new(move |_, description| {
+    ///     println!("GLFW error {}: {}", error_count, description);
+    ///     error_count += 1;
+    /// }) as Box<FnMut(glfw::Error, String) + Send>));
     ///
     /// // removes the previously set callback
     /// glfw.set_error_callback(None);
     /// ~~~
     ///
-    /// The `FAIL_ON_ERRORS` and `LOG_ERRORS` callbacks are provided for
-    /// convenience. For example:
-    ///
-    /// ~~~ignore
-    /// // triggers a task failure when a GLFW error is encountered.
-    /// glfw.set_error_callback(glfw::FAIL_ON_ERRORS);
-    /// ~~~
-    pub fn set_error_callback<UserData: 'static>(&mut self, callback: Option<ErrorCallback<UserData>>) {
+    /// The `FAIL_ON_ERRORS` and `LOG_ERRORS` callbacks can be supplied to
+    /// `glfw::init` for convenience.
+    pub fn set_error_callback(&mut self, callback: Option<Box<FnMut(Error, String) + Send>>) {
         match callback {
             Some(f) => callbacks::error::set(f),
             None    => callbacks::error::unset(),
@@ -481,7 +761,7 @@ impl Glfw {
     ///         m.map_or(glfw::WindowMode::Windowed, |m| glfw::FullScreen(m)))
     /// }).expect("Failed to create GLFW window.");
     /// ~~~
-    pub fn with_primary_monitor<T, F>(&mut self, f: F) -> T where F: Fn(&mut Self, Option<&Monitor>) -> T {
+    pub fn with_primary_monitor<T, F>(&mut self, f: F) -> T where F: FnOnce(&mut Self, Option<&Monitor>) -> T {
         match unsafe { ffi::glfwGetPrimaryMonitor() } {
             ptr if ptr.is_null() => f(self, None),
             ptr => f(self, Some(&Monitor {
@@ -490,6 +770,54 @@ impl Glfw {
         }
     }
 
+    /// Returns a receiver for monitor connection and disconnection events,
+    /// along with the time at which they occurred and a handle to the monitor
+    /// concerned. Events are only delivered while polling or waiting for
+    /// events on the main thread. Calling this again replaces the previous
+    /// receiver, which will no longer receive any events.
+    ///
+    /// The handle sent along with a `MonitorEvent::Disconnected` event is
+    /// already invalidated by the time it is received, but can still be
+    /// compared against stored handles.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// let monitor_events = glfw.monitor_events();
+    /// loop {
+    ///     glfw.poll_events();
+    ///     for (_, event, handle) in glfw::flush_messages(&monitor_events) {
+    ///         println!("{:?}: {:?}", handle, event);
+    ///     }
+    /// }
+    /// ~~~
+    pub fn monitor_events(&mut self) -> Receiver<(f64, MonitorEvent, MonitorHandle)> {
+        let (sender, receiver) = channel();
+        callbacks::monitor::set_sender(sender);
+        receiver
+    }
+
+    /// Returns a handle to the primary monitor, if it exists. Unlike the
+    /// monitor supplied by `with_primary_monitor`, the handle can be stored
+    /// and used later on.
+    pub fn get_primary_monitor_handle(&self) -> Option<MonitorHandle> {
+        match unsafe { ffi::glfwGetPrimaryMonitor() } {
+            ptr if ptr.is_null() => None,
+            ptr => Some(MonitorHandle::from_ptr(ptr)),
+        }
+    }
+
+    /// Returns handles to the currently connected monitors.
+    pub fn get_connected_monitor_handles(&self) -> Vec<MonitorHandle> {
+        unsafe {
+            let mut count = 0;
+            let ptr = ffi::glfwGetMonitors(&mut count);
+            slice::from_raw_parts(ptr as *const _, count as usize).iter().map(|&ptr| {
+                MonitorHandle::from_ptr(ptr)
+            }).collect()
+        }
+    }
+
     /// Supplies a vector of the currently connected monitors to the closure
     /// provided.
     ///
@@ -502,7 +830,7 @@ impl Glfw {
     ///     }
     /// });
     /// ~~~
-    pub fn with_connected_monitors<T, F>(&mut self, f: F) -> T where F: Fn(&mut Self, &[Monitor]) -> T {
+    pub fn with_connected_monitors<T, F>(&mut self, f: F) -> T where F: FnOnce(&mut Self, &[Monitor]) -> T {
         unsafe {
             let mut count = 0;
             let ptr = ffi::glfwGetMonitors(&mut count);
@@ -546,21 +874,22 @@ impl Glfw {
     /// ~~~
     pub fn window_hint(&mut self, hint: WindowHint) {
         match hint {
-            WindowHint::RedBits(bits)                   => unsafe { ffi::glfwWindowHint(ffi::RED_BITS,              bits as c_int) },
-            WindowHint::GreenBits(bits)                 => unsafe { ffi::glfwWindowHint(ffi::GREEN_BITS,            bits as c_int) },
-            WindowHint::BlueBits(bits)                  => unsafe { ffi::glfwWindowHint(ffi::BLUE_BITS,             bits as c_int) },
-            WindowHint::AlphaBits(bits)                 => unsafe { ffi::glfwWindowHint(ffi::ALPHA_BITS,            bits as c_int) },
-            WindowHint::DepthBits(bits)                 => unsafe { ffi::glfwWindowHint(ffi::DEPTH_BITS,            bits as c_int) },
-            WindowHint::StencilBits(bits)               => unsafe { ffi::glfwWindowHint(ffi::STENCIL_BITS,          bits as c_int) },
-            WindowHint::AccumRedBits(bits)              => unsafe { ffi::glfwWindowHint(ffi::ACCUM_RED_BITS,        bits as c_int) },
-            WindowHint::AccumGreenBits(bits)            => unsafe { ffi::glfwWindowHint(ffi::ACCUM_GREEN_BITS,      bits as c_int) },
-            WindowHint::AccumBlueBits(bits)             => unsafe { ffi::glfwWindowHint(ffi::ACCUM_BLUE_BITS,       bits as c_int) },
-            WindowHint::AccumAlphaBits(bits)            => unsafe { ffi::glfwWindowHint(ffi::ACCUM_ALPHA_BITS,      bits as c_int) },
-            WindowHint::AuxBuffers(num_buffers)         => unsafe { ffi::glfwWindowHint(ffi::AUX_BUFFERS,           num_buffers as c_int) },
+            WindowHint::RedBits(bits)                   => unsafe { ffi::glfwWindowHint(ffi::RED_BITS,              dont_care_or(bits)) },
+            WindowHint::GreenBits(bits)                 => unsafe { ffi::glfwWindowHint(ffi::GREEN_BITS,            dont_care_or(bits)) },
+            WindowHint::BlueBits(bits)                  => unsafe { ffi::glfwWindowHint(ffi::BLUE_BITS,             dont_care_or(bits)) },
+            WindowHint::AlphaBits(bits)                 => unsafe { ffi::glfwWindowHint(ffi::ALPHA_BITS,            dont_care_or(bits)) },
+            WindowHint::DepthBits(bits)                 => unsafe { ffi::glfwWindowHint(ffi::DEPTH_BITS,            dont_care_or(bits)) },
+            WindowHint::StencilBits(bits)               => unsafe { ffi::glfwWindowHint(ffi::STENCIL_BITS,          dont_care_or(bits)) },
+            WindowHint::AccumRedBits(bits)              => unsafe { ffi::glfwWindowHint(ffi::ACCUM_RED_BITS,        dont_care_or(bits)) },
+            WindowHint::AccumGreenBits(bits)            => unsafe { ffi::glfwWindowHint(ffi::ACCUM_GREEN_BITS,      dont_care_or(bits)) },
+            WindowHint::AccumBlueBits(bits)             => unsafe { ffi::glfwWindowHint(ffi::ACCUM_BLUE_BITS,       dont_care_or(bits)) },
+            WindowHint::AccumAlphaBits(bits)            => unsafe { ffi::glfwWindowHint(ffi::ACCUM_ALPHA_BITS,      dont_care_or(bits)) },
+            WindowHint::AuxBuffers(num_buffers)         => unsafe { ffi::glfwWindowHint(ffi::AUX_BUFFERS,           dont_care_or(num_buffers)) },
             WindowHint::Stereo(is_stereo)               => unsafe { ffi::glfwWindowHint(ffi::STEREO,                is_stereo as c_int) },
-            WindowHint::Samples(num_samples)            => unsafe { ffi::glfwWindowHint(ffi::SAMPLES,               num_samples as c_int) },
+            WindowHint::Samples(num_samples)            => unsafe { ffi::glfwWindowHint(ffi::SAMPLES,               dont_care_or(num_samples)) },
             WindowHint::SRgbCapable(is_capable)         => unsafe { ffi::glfwWindowHint(ffi::SRGB_CAPABLE,          is_capable as c_int) },
-            WindowHint::RefreshRate(rate)               => unsafe { ffi::glfwWindowHint(ffi::REFRESH_RATE,          rate as c_int) },
+            WindowHint::DoubleBuffer(double_buffer)     => unsafe { ffi::glfwWindowHint(ffi::DOUBLEBUFFER,          double_buffer as c_int) },
+            WindowHint::RefreshRate(rate)               => unsafe { ffi::glfwWindowHint(ffi::REFRESH_RATE,          dont_care_or(rate)) },
             WindowHint::ClientApi(api)                  => unsafe { ffi::glfwWindowHint(ffi::CLIENT_API,            api as c_int) },
             WindowHint::ContextVersionMajor(major)      => unsafe { ffi::glfwWindowHint(ffi::CONTEXT_VERSION_MAJOR, major as c_int) },
             WindowHint::ContextVersionMinor(minor)      => unsafe { ffi::glfwWindowHint(ffi::CONTEXT_VERSION_MINOR, minor as c_int) },
@@ -570,11 +899,14 @@ impl Glfw {
             WindowHint::OpenglForwardCompat(is_compat)  => unsafe { ffi::glfwWindowHint(ffi::OPENGL_FORWARD_COMPAT, is_compat as c_int) },
             WindowHint::OpenglDebugContext(is_debug)    => unsafe { ffi::glfwWindowHint(ffi::OPENGL_DEBUG_CONTEXT,  is_debug as c_int) },
             WindowHint::OpenglProfile(profile)          => unsafe { ffi::glfwWindowHint(ffi::OPENGL_PROFILE,        profile as c_int) },
+            WindowHint::ContextReleaseBehavior(release) => unsafe { ffi::glfwWindowHint(ffi::CONTEXT_RELEASE_BEHAVIOR, release as c_int) },
             WindowHint::Resizable(is_resizable)         => unsafe { ffi::glfwWindowHint(ffi::RESIZABLE,             is_resizable as c_int) },
             WindowHint::Visible(is_visible)             => unsafe { ffi::glfwWindowHint(ffi::VISIBLE,               is_visible as c_int) },
             WindowHint::Decorated(is_decorated)         => unsafe { ffi::glfwWindowHint(ffi::DECORATED,             is_decorated as c_int) },
             WindowHint::AutoIconify(auto_iconify)       => unsafe { ffi::glfwWindowHint(ffi::AUTO_ICONIFY,          auto_iconify as c_int) },
             WindowHint::Floating(is_floating)           => unsafe { ffi::glfwWindowHint(ffi::FLOATING,              is_floating as c_int) },
+            #[cfg(feature = "glfw-3-2")]
+            WindowHint::Maximized(is_maximized)         => unsafe { ffi::glfwWindowHint(ffi::MAXIMIZED,             is_maximized as c_int) },
         }
     }
 
@@ -593,6 +925,41 @@ impl Glfw {
         self.create_window_intern(width, height, title, mode, None)
     }
 
+    /// Creates a new window, returning the error reported by GLFW if this
+    /// failed. The error is not passed to the error callback.
+    ///
+    /// Wrapper for `glfwCreateWindow`.
+    pub fn try_create_window(&self, width: u32, height: u32, title: &str, mode: WindowMode) -> Result<(Window, Receiver<(f64, WindowEvent)>), GlfwError> {
+        self.try_create_window_intern(width, height, title, mode, None)
+    }
+
+    /// Internal wrapper for `glfwCreateWindow` that captures the errors
+    /// reported during window creation. If creation fails, the last error is
+    /// returned and any earlier ones are passed to the error callback.
+    fn try_create_window_intern(&self, width: u32, height: u32, title: &str, mode: WindowMode, share: Option<&Window>) -> Result<(Window, Receiver<(f64, WindowEvent)>), GlfwError> {
+        let (result, mut errors) = callbacks::error::capture(|| {
+            self.create_window_intern(width, height, title, mode, share)
+        });
+        let last_error = match result {
+            Some(_) => None,
+            None    => errors.pop(),
+        };
+        for (error, description) in errors.into_iter() {
+            callbacks::error::report(error, description);
+        }
+        match (result, last_error) {
+            (Some(window), _) => Ok(window),
+            (None, Some((error, description))) => Err(GlfwError {
+                error: error,
+                description: description,
+            }),
+            (None, None) => Err(GlfwError {
+                error: Error::PlatformError,
+                description: "Window creation failed without GLFW reporting an error".to_string(),
+            }),
+        }
+    }
+
     /// Internal wrapper for `glfwCreateWindow`.
     fn create_window_intern(&self, width: u32, height: u32, title: &str, mode: WindowMode, share: Option<&Window>) -> Option<(Window, Receiver<(f64, WindowEvent)>)> {
         let ptr = unsafe {
@@ -618,13 +985,49 @@ impl Glfw {
                     glfw: self.clone(),
                     is_shared: share.is_none(),
                     drop_sender: Some(drop_sender),
-                    drop_receiver: drop_receiver
+                    drop_receiver: drop_receiver,
+                    current_cursor: None,
+                    standard_cursors: HashMap::new(),
+                    windowed_geometry: None,
                 },
                 receiver,
             ))
         }
     }
 
+    /// Creates a custom cursor from an RGBA image. The hotspot is specified in
+    /// pixels, relative to the top-left corner of the image. Returns `None` if
+    /// the cursor could not be created.
+    ///
+    /// Wrapper for `glfwCreateCursor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `image.pixels` does not contain exactly
+    /// `image.width * image.height * 4` bytes.
+    pub fn create_cursor(&self, image: &PixelImage, xhot: u32, yhot: u32) -> Option<Cursor> {
+        let glfw_image = image.to_glfw_image();
+        let ptr = unsafe { ffi::glfwCreateCursor(&glfw_image, xhot as c_int, yhot as c_int) };
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Cursor { ptr: ptr })
+        }
+    }
+
+    /// Creates a cursor with a standard shape. Returns `None` if the cursor
+    /// could not be created.
+    ///
+    /// Wrapper for `glfwCreateStandardCursor`.
+    pub fn create_standard_cursor(&self, shape: StandardCursor) -> Option<Cursor> {
+        let ptr = unsafe { ffi::glfwCreateStandardCursor(shape as c_int) };
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Cursor { ptr: ptr })
+        }
+    }
+
     /// Makes the context of the specified window current. If no window is given
     /// then the current context is detached.
     ///
@@ -657,6 +1060,41 @@ impl Glfw {
         unsafe { ffi::glfwWaitEvents(); }
     }
 
+    /// Posts an empty event to the event queue, causing `Glfw::wait_events` to
+    /// return. Use a `WakeupSender` to do this from another thread.
+    ///
+    /// Wrapper for `glfwPostEmptyEvent`.
+    pub fn post_empty_event(&self) {
+        unsafe { ffi::glfwPostEmptyEvent(); }
+    }
+
+    /// Creates a channel that other threads can use to wake up the main thread
+    /// when it is blocked in `Glfw::wait_events`, optionally passing along a
+    /// message. The messages can be handled alongside window events using
+    /// `glfw::flush_messages`.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// let (wakeup, messages) = glfw.create_wakeup_channel();
+    ///
+    /// std::thread::spawn(move || {
+    ///     // do some work, then notify the main thread
+    ///     wakeup.send("work done").unwrap();
+    /// });
+    ///
+    /// loop {
+    ///     glfw.wait_events();
+    ///     for message in glfw::flush_messages(&messages) {
+    ///         println!("{}", message);
+    ///     }
+    /// }
+    /// ~~~
+    pub fn create_wakeup_channel<T: Send>(&self) -> (WakeupSender<T>, Receiver<T>) {
+        let (sender, receiver) = channel();
+        (WakeupSender { sender: sender }, receiver)
+    }
+
     /// Returns the current value of the GLFW timer. Unless the timer has been
     /// set using `glfw::set_time`, the timer measures time elapsed since GLFW
     /// was initialized.
@@ -713,6 +1151,48 @@ impl Glfw {
     pub fn get_joystick(&self, id: JoystickId) -> Joystick {
         Joystick { id: id, glfw: self.clone() }
     }
+
+    /// Returns a receiver for joystick connection and disconnection events,
+    /// along with the time at which they occurred. Events are only delivered
+    /// while polling or waiting for events on the main thread. Calling this
+    /// again replaces the previous receiver, which will no longer receive any
+    /// events.
+    ///
+    /// Wrapper for `glfwSetJoystickCallback`. Requires GLFW 3.2.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// let joystick_events = glfw.joystick_events();
+    /// loop {
+    ///     glfw.poll_events();
+    ///     for (_, id, event) in glfw::flush_messages(&joystick_events) {
+    ///         println!("{:?}: {:?}", id, event);
+    ///     }
+    /// }
+    /// ~~~
+    #[cfg(feature = "glfw-3-2")]
+    pub fn joystick_events(&mut self) -> Receiver<(f64, JoystickId, JoystickEvent)> {
+        let (sender, receiver) = channel();
+        callbacks::joystick::set_sender(sender);
+        receiver
+    }
+
+    /// Sets a callback that is called with the id and name of each joystick
+    /// that is connected, overwriting the previous one stored. The callback
+    /// is called on the main thread while polling or waiting for events.
+    ///
+    /// Wrapper for `glfwSetJoystickCallback`. Requires GLFW 3.2.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn on_joystick_connected(&mut self, callback: Option<Box<FnMut(JoystickId, String)>>) {
+        callbacks::joystick::set_connected(callback);
+    }
+}
+
+/// Converts an optional value to the value expected by GLFW functions such as
+/// `glfwWindowHint`, mapping `None` to `GLFW_DONT_CARE`.
+fn dont_care_or(value: Option<u32>) -> c_int {
+    value.map_or(ffi::DONT_CARE, |value| value as c_int)
 }
 
 /// Wrapper for `glfwGetVersion`.
@@ -764,7 +1244,90 @@ impl std::fmt::Debug for Monitor {
     }
 }
 
+/// A handle to a monitor that can be stored and compared, unlike `Monitor`,
+/// which is only valid for the duration of the closure it is supplied to.
+///
+/// Handles are invalidated once the monitor they refer to is disconnected,
+/// after any monitor callback has been called with the
+/// `MonitorEvent::Disconnected` event.
+///
+/// GLFW monitors may only be used on the main thread, so `MonitorHandle`
+/// cannot be sent to other threads.
+#[derive(Copy, Clone)]
+pub struct MonitorHandle {
+    id: usize,
+    /// Makes the handle `!Send`.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &MonitorHandle) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl fmt::Debug for MonitorHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MonitorHandle({})", self.id)
+    }
+}
+
+/// The error returned when using a `MonitorHandle` whose monitor has been
+/// disconnected.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MonitorDisconnected;
+
+impl fmt::Display for MonitorDisconnected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The monitor has been disconnected")
+    }
+}
+
+impl MonitorHandle {
+    fn from_ptr(ptr: *mut ffi::GLFWmonitor) -> MonitorHandle {
+        MonitorHandle {
+            id: callbacks::monitor::handle_id(ptr),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Supplies the monitor referred to by the handle to the closure
+    /// provided, or returns `Err(MonitorDisconnected)` if it has been
+    /// disconnected.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// let handle = glfw.get_primary_monitor_handle().unwrap();
+    /// // ...
+    /// match handle.with_monitor(|m| m.get_name()) {
+    ///     Ok(name) => println!("Still connected: {}", name),
+    ///     Err(e) => println!("{}", e),
+    /// }
+    /// ~~~
+    pub fn with_monitor<T, F>(&self, f: F) -> Result<T, MonitorDisconnected> where F: FnOnce(&mut Monitor) -> T {
+        match callbacks::monitor::handle_ptr(self.id) {
+            Some(ptr) => Ok(f(&mut Monitor { ptr: ptr })),
+            None => Err(MonitorDisconnected),
+        }
+    }
+}
+
 impl Monitor {
+    /// Returns a handle to this monitor that remains usable outside of the
+    /// closure the monitor was supplied to.
+    pub fn handle(&self) -> MonitorHandle {
+        MonitorHandle::from_ptr(self.ptr)
+    }
+
     /// Wrapper for `glfwGetMonitorPos`.
     pub fn get_pos(&self) -> (i32, i32) {
         unsafe {
@@ -775,6 +1338,21 @@ impl Monitor {
         }
     }
 
+    /// Wrapper for `glfwGetMonitorWorkarea`. Returns the position and size of
+    /// the area of the monitor not occupied by task bars, docks and the like,
+    /// as `(xpos, ypos, width, height)`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_workarea(&self) -> (i32, i32, i32, i32) {
+        unsafe {
+            let mut xpos = 0;
+            let mut ypos = 0;
+            let mut width = 0;
+            let mut height = 0;
+            ffi::glfwGetMonitorWorkarea(self.ptr, &mut xpos, &mut ypos, &mut width, &mut height);
+            (xpos as i32, ypos as i32, width as i32, height as i32)
+        }
+    }
+
     /// Wrapper for `glfwGetMonitorPhysicalSize`.
     pub fn get_physical_size(&self) -> (i32, i32) {
         unsafe {
@@ -785,6 +1363,33 @@ impl Monitor {
         }
     }
 
+    /// Returns the horizontal and vertical dots per inch of the monitor,
+    /// computed from its physical size and current video mode. Returns `None`
+    /// if either of these is unknown. Note that some systems do not report
+    /// the physical size accurately, in which case `get_content_scale` should
+    /// be preferred.
+    pub fn dpi(&self) -> Option<(f32, f32)> {
+        let (width_mm, height_mm) = self.get_physical_size();
+        match self.get_video_mode() {
+            Some(mode) if width_mm > 0 && height_mm > 0 => Some((
+                mode.width as f32 * 25.4 / width_mm as f32,
+                mode.height as f32 * 25.4 / height_mm as f32,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Wrapper for `glfwGetMonitorContentScale`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_content_scale(&self) -> (f32, f32) {
+        unsafe {
+            let mut xscale = 0.0;
+            let mut yscale = 0.0;
+            ffi::glfwGetMonitorContentScale(self.ptr, &mut xscale, &mut yscale);
+            (xscale as f32, yscale as f32)
+        }
+    }
+
     /// Wrapper for `glfwGetMonitorName`.
     pub fn get_name(&self) -> String {
         unsafe { string_from_c_str(ffi::glfwGetMonitorName(self.ptr)) }
@@ -826,19 +1431,46 @@ impl Monitor {
         }
     }
 
-    /// Wrapper for `glfwSetGammaRamp`.
-    pub fn set_gamma_ramp(&mut self, ramp: &mut GammaRamp) {
+    /// Wrapper for `glfwSetGammaRamp`. Returns an error without changing
+    /// the ramp if the channels are empty or of differing lengths.
+    pub fn set_gamma_ramp(&mut self, ramp: &GammaRamp) -> Result<(), GammaRampError> {
+        try!(ramp.validate());
         unsafe {
+            // GLFW does not modify the ramp, despite the pointers being
+            // mutable.
             ffi::glfwSetGammaRamp(
                 self.ptr,
                 &ffi::GLFWgammaramp {
-                    red:    ramp.red.as_mut_ptr(),
-                    green:  ramp.green.as_mut_ptr(),
-                    blue:   ramp.blue.as_mut_ptr(),
+                    red:    ramp.red.as_ptr() as *mut c_ushort,
+                    green:  ramp.green.as_ptr() as *mut c_ushort,
+                    blue:   ramp.blue.as_ptr() as *mut c_ushort,
                     size:   ramp.red.len() as u32,
                 }
             );
         }
+        Ok(())
+    }
+
+    /// Saves the current gamma ramp of the monitor, so that it is restored
+    /// when the returned value is dropped.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// let monitor = glfw.get_primary_monitor_handle().unwrap();
+    /// let saved = monitor.with_monitor(|m| {
+    ///     let saved = m.save_gamma_ramp();
+    ///     let size = saved.ramp().red.len();
+    ///     m.set_gamma_ramp(&glfw::GammaRamp::from_color_temperature(size, 3400.0)).unwrap();
+    ///     saved
+    /// }).unwrap();
+    /// // the original ramp is restored when `saved` goes out of scope
+    /// ~~~
+    pub fn save_gamma_ramp(&self) -> SavedGammaRamp {
+        SavedGammaRamp {
+            monitor: self.handle(),
+            ramp: self.get_gamma_ramp(),
+        }
     }
 }
 
@@ -883,42 +1515,49 @@ impl fmt::Debug for VidMode {
 }
 
 /// Window hints that can be set using the `window_hint` function.
+///
+/// Hints that take an `Option` can be set to `None` to indicate that the
+/// application has no preference, corresponding to `GLFW_DONT_CARE`.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum WindowHint {
     /// Specifies the desired bit depth of the red component of the default framebuffer.
-    RedBits(u32),
+    RedBits(Option<u32>),
     /// Specifies the desired bit depth of the green component of the default framebuffer.
-    GreenBits(u32),
+    GreenBits(Option<u32>),
     /// Specifies the desired bit depth of the blue component of the default framebuffer.
-    BlueBits(u32),
+    BlueBits(Option<u32>),
     /// Specifies the desired bit depth of the alpha component of the default framebuffer.
-    AlphaBits(u32),
+    AlphaBits(Option<u32>),
     /// Specifies the desired bit depth of the depth component of the default framebuffer.
-    DepthBits(u32),
+    DepthBits(Option<u32>),
     /// Specifies the desired bit depth of the stencil component of the default framebuffer.
-    StencilBits(u32),
+    StencilBits(Option<u32>),
     /// Specifies the desired bit depth of the red component of the accumulation framebuffer.
-    AccumRedBits(u32),
+    AccumRedBits(Option<u32>),
     /// Specifies the desired bit depth of the green component of the accumulation framebuffer.
-    AccumGreenBits(u32),
+    AccumGreenBits(Option<u32>),
     /// Specifies the desired bit depth of the blue component of the accumulation framebuffer.
-    AccumBlueBits(u32),
+    AccumBlueBits(Option<u32>),
     /// Specifies the desired bit depth of the alpha component of the accumulation framebuffer.
-    AccumAlphaBits(u32),
+    AccumAlphaBits(Option<u32>),
     /// Specifies the desired number of auxiliary buffers.
-    AuxBuffers(u32),
+    AuxBuffers(Option<u32>),
     /// Specifies whether to use stereoscopic rendering.
     Stereo(bool),
     /// Specifies the desired number of samples to use for multisampling. Zero
     /// disables multisampling.
-    Samples(u32),
+    Samples(Option<u32>),
     /// Specifies whether the framebuffer should be sRGB capable.
     SRgbCapable(bool),
+    /// Specifies whether the framebuffer should be double buffered. Single
+    /// buffered windows draw directly to the front buffer, so
+    /// `Context::swap_buffers` should not be used with them.
+    DoubleBuffer(bool),
     /// Specifies the desired refresh rate for full screen windows. If set to
-    /// zero, the highest available refresh rate will be used.
+    /// `None`, the highest available refresh rate will be used.
     ///
     /// This hint is ignored for windowed mode windows.
-    RefreshRate(u32),
+    RefreshRate(Option<u32>),
     /// Specifies which `ClientApi` to create the context for.
     ClientApi(ClientApiHint),
     /// Specifies the major client API version that the created context must be
@@ -962,6 +1601,9 @@ pub enum WindowHint {
     ///
     /// If another client API is requested, this hint is ignored.
     OpenglProfile(OpenGlProfileHint),
+    /// Specifies the `ContextReleaseBehaviorHint` to be used when the context
+    /// is made non-current.
+    ContextReleaseBehavior(ContextReleaseBehaviorHint),
     /// Specifies whether the window will be resizable by the user. Even if this
     /// is set to `false`, the window can still be resized using the
     /// `Window::set_size` function.
@@ -987,6 +1629,12 @@ pub enum WindowHint {
     ///
     /// This hint is ignored for full screen windows.
     Floating(bool),
+    /// Specifies whether the window will be maximized when created. Requires
+    /// GLFW 3.2.
+    ///
+    /// This hint is ignored for full screen windows.
+    #[cfg(feature = "glfw-3-2")]
+    Maximized(bool),
 }
 
 /// Client API tokens.
@@ -1006,6 +1654,19 @@ pub enum ContextRobustnessHint {
     LoseContextOnReset          = ffi::LOSE_CONTEXT_ON_RESET,
 }
 
+/// Context release behavior tokens.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum ContextReleaseBehaviorHint {
+    /// The default behavior of the context creation API will be used.
+    Any                         = ffi::ANY_RELEASE_BEHAVIOR,
+    /// The pipeline will be flushed whenever the context is released from
+    /// being the current one.
+    Flush                       = ffi::RELEASE_BEHAVIOR_FLUSH,
+    /// The pipeline will not be flushed on release.
+    None                        = ffi::RELEASE_BEHAVIOR_NONE,
+}
+
 /// OpenGL profile tokens.
 #[repr(i32)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
@@ -1037,6 +1698,206 @@ impl<'a> WindowMode<'a> {
     }
 }
 
+/// Collects the window hints and parameters needed to create a window.
+///
+/// Window hints are global state in GLFW, so hints set using
+/// `Glfw::window_hint` leak into every subsequent call to
+/// `Glfw::create_window`. A `WindowBuilder` instead resets the hints to their
+/// default values, applies its own hints, creates the window and then resets
+/// the hints again, so that it does not depend on hints set elsewhere.
+///
+/// Note that this means creating a window through the builder resets the
+/// global hints to their defaults: any hints previously set with
+/// `Glfw::window_hint` are lost, and must be set again before the next call
+/// to `Glfw::create_window`.
+///
+/// # Example
+///
+/// ~~~ignore
+/// let (window, events) = glfw::WindowBuilder::new(&glfw, 300, 300, "Hello this is window")
+///     .hint(glfw::WindowHint::ContextVersion(3, 2))
+///     .hint(glfw::WindowHint::OpenglForwardCompat(true))
+///     .hint(glfw::WindowHint::OpenglProfile(glfw::OpenGlProfileHint::Core))
+///     .create()
+///     .expect("Failed to create GLFW window.");
+/// ~~~
+pub struct WindowBuilder<'a> {
+    glfw: Glfw,
+    width: u32,
+    height: u32,
+    title: &'a str,
+    mode: WindowMode<'a>,
+    share: Option<&'a Window>,
+    hints: Vec<WindowHint>,
+}
+
+impl<'a> WindowBuilder<'a> {
+    /// Starts building a windowed mode window with the default window hints.
+    pub fn new(glfw: &Glfw, width: u32, height: u32, title: &'a str) -> WindowBuilder<'a> {
+        WindowBuilder {
+            glfw: glfw.clone(),
+            width: width,
+            height: height,
+            title: title,
+            mode: WindowMode::Windowed,
+            share: None,
+            hints: Vec::new(),
+        }
+    }
+
+    /// Adds a hint to be applied when the window is created. Later hints
+    /// override earlier ones of the same kind.
+    pub fn hint(mut self, hint: WindowHint) -> WindowBuilder<'a> {
+        self.hints.push(hint);
+        self
+    }
+
+    /// Adds several hints to be applied when the window is created.
+    pub fn hints(mut self, hints: &[WindowHint]) -> WindowBuilder<'a> {
+        self.hints.extend(hints.iter().cloned());
+        self
+    }
+
+    /// Sets whether the window is created in fullscreen or windowed mode.
+    pub fn mode(mut self, mode: WindowMode<'a>) -> WindowBuilder<'a> {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the window whose context will share its objects with the context
+    /// of the created window.
+    pub fn share(mut self, share: &'a Window) -> WindowBuilder<'a> {
+        self.share = Some(share);
+        self
+    }
+
+    /// Creates the window, returning `None` if this failed. The window hints
+    /// are reset to their default values afterwards.
+    ///
+    /// Wrapper for `glfwDefaultWindowHints`, `glfwWindowHint` and
+    /// `glfwCreateWindow`.
+    pub fn create(self) -> Option<(Window, Receiver<(f64, WindowEvent)>)> {
+        self.create_intern(&[])
+    }
+
+    /// Attempts to create the window with each of the supplied context
+    /// configurations in turn, stopping at the first one that succeeds. The
+    /// hints of each configuration are applied after the hints added to the
+    /// builder.
+    ///
+    /// Errors reported by GLFW during a failed attempt, such as
+    /// `Error::VersionUnavailable` or `Error::FormatUnavailable`, are
+    /// collected in the returned `ContextFallback` rather than being passed to
+    /// the error callback.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// let fallback = glfw::WindowBuilder::new(&glfw, 300, 300, "Hello this is window")
+    ///     .create_with_fallbacks(&[
+    ///         glfw::ContextConfig::new(4, 5, glfw::OpenGlProfileHint::Core),
+    ///         glfw::ContextConfig::new(3, 3, glfw::OpenGlProfileHint::Core),
+    ///         glfw::ContextConfig::new(2, 1, glfw::OpenGlProfileHint::Any),
+    ///     ]);
+    /// for &(config, ref errors) in fallback.failures.iter() {
+    ///     println!("{:?} unavailable: {:?}", config, errors);
+    /// }
+    /// let (window, events, config) = fallback.window.expect("Failed to create GLFW window.");
+    /// ~~~
+    pub fn create_with_fallbacks(self, configs: &[ContextConfig]) -> ContextFallback {
+        let mut failures = Vec::new();
+        for &config in configs.iter() {
+            let (result, errors) = callbacks::error::capture(|| {
+                self.create_intern(&config.to_hints())
+            });
+            match result {
+                Some((window, events)) => {
+                    for (error, description) in errors.into_iter() {
+                        callbacks::error::report(error, description);
+                    }
+                    return ContextFallback {
+                        window: Some((window, events, config)),
+                        failures: failures,
+                    };
+                }
+                None => failures.push((config, errors)),
+            }
+        }
+        ContextFallback { window: None, failures: failures }
+    }
+
+    /// Applies the builder's hints followed by `extra_hints`, creates the
+    /// window and then restores the default window hints.
+    fn create_intern(&self, extra_hints: &[WindowHint]) -> Option<(Window, Receiver<(f64, WindowEvent)>)> {
+        let mut glfw = self.glfw;
+        glfw.default_window_hints();
+        for &hint in self.hints.iter().chain(extra_hints.iter()) {
+            glfw.window_hint(hint);
+        }
+        let result = glfw.create_window_intern(self.width, self.height, self.title, self.mode, self.share);
+        glfw.default_window_hints();
+        result
+    }
+}
+
+/// A context configuration to try when creating a window using
+/// `WindowBuilder::create_with_fallbacks`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ContextConfig {
+    /// The major and minor client API version, applied as a
+    /// `WindowHint::ContextVersion`.
+    pub version: (u32, u32),
+    /// The OpenGL profile, applied as a `WindowHint::OpenglProfile`.
+    pub profile: OpenGlProfileHint,
+    /// Whether the context should be forward-compatible, applied as a
+    /// `WindowHint::OpenglForwardCompat`.
+    pub forward_compat: bool,
+    /// The number of samples to use for multisampling, applied as a
+    /// `WindowHint::Samples` if set. When `None`, the samples hint set on
+    /// the `WindowBuilder`, if any, is used instead.
+    pub samples: Option<u32>,
+}
+
+impl ContextConfig {
+    /// Creates a configuration for the given version and profile. Core
+    /// profile configurations are made forward-compatible, as required by
+    /// OS X, and multisampling is left to the hints of the `WindowBuilder`.
+    pub fn new(major: u32, minor: u32, profile: OpenGlProfileHint) -> ContextConfig {
+        ContextConfig {
+            version: (major, minor),
+            profile: profile,
+            forward_compat: profile == OpenGlProfileHint::Core,
+            samples: None,
+        }
+    }
+
+    /// Returns the window hints corresponding to this configuration. These
+    /// are applied after, and so override, the hints of the `WindowBuilder`.
+    pub fn to_hints(&self) -> Vec<WindowHint> {
+        let (major, minor) = self.version;
+        let mut hints = vec![
+            WindowHint::ContextVersion(major, minor),
+            WindowHint::OpenglProfile(self.profile),
+            WindowHint::OpenglForwardCompat(self.forward_compat),
+        ];
+        if let Some(samples) = self.samples {
+            hints.push(WindowHint::Samples(Some(samples)));
+        }
+        hints
+    }
+}
+
+/// The outcome of `WindowBuilder::create_with_fallbacks`.
+pub struct ContextFallback {
+    /// The created window and its event receiver, along with the
+    /// configuration that was used to create it. This is `None` if every
+    /// configuration failed.
+    pub window: Option<(Window, Receiver<(f64, WindowEvent)>, ContextConfig)>,
+    /// The configurations that failed, in the order they were attempted, each
+    /// with the errors that GLFW reported while trying it.
+    pub failures: Vec<(ContextConfig, Vec<(Error, String)>)>,
+}
+
 bitflags! {
     #[doc = "Key modifiers"]
     flags Modifiers: c_int {
@@ -1061,10 +1922,35 @@ impl fmt::Debug for Modifiers {
     }
 }
 
+bitflags! {
+    #[doc = "Joystick hat directions. A hat that is not pushed in any"]
+    #[doc = "direction has no flags set, and diagonals have two flags set."]
+    flags JoystickHats: c_int {
+        const HatUp       = ffi::HAT_UP,
+        const HatRight    = ffi::HAT_RIGHT,
+        const HatDown     = ffi::HAT_DOWN,
+        const HatLeft     = ffi::HAT_LEFT
+    }
+}
+
+impl fmt::Debug for JoystickHats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, x) in [HatUp, HatRight, HatDown, HatLeft].iter().filter(|x| self.contains(**x)).enumerate() {
+            if i != 0 { try!(write!(f, ", ")) };
+            if      *x == HatUp    { try!(write!(f, "Up"    )) }
+            else if *x == HatRight { try!(write!(f, "Right" )) }
+            else if *x == HatDown  { try!(write!(f, "Down"  )) }
+            else if *x == HatLeft  { try!(write!(f, "Left"  )) }
+            else                   { try!(write!(f, "???"   )) }
+        }
+        Ok(())
+    }
+}
+
 pub type Scancode = c_int;
 
 /// Window event messages.
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
 pub enum WindowEvent {
     Pos(i32, i32),
     Size(i32, i32),
@@ -1072,13 +1958,19 @@ pub enum WindowEvent {
     Refresh,
     Focus(bool),
     Iconify(bool),
+    /// Only emitted when built with the `glfw-3-3` feature.
+    Maximize(bool),
     FramebufferSize(i32, i32),
+    /// Only emitted when built with the `glfw-3-3` feature.
+    ContentScale(f32, f32),
     MouseButton(MouseButton, Action, Modifiers),
     CursorPos(f64, f64),
     CursorEnter(bool),
     Scroll(f64, f64),
     Key(Key, Scancode, Action, Modifiers),
     Char(char),
+    CharModifiers(char, Modifiers),
+    FileDrop(Vec<PathBuf>),
 }
 
 /// Returns an iterator that yeilds until no more messages are contained in the
@@ -1092,18 +1984,18 @@ pub enum WindowEvent {
 ///     // handle event
 /// }
 /// ~~~
-pub fn flush_messages<'a, Message: Send>(receiver: &'a Receiver<Message>) -> FlushedMessages<'a, Message> {
+pub fn flush_messages<'a, Message>(receiver: &'a Receiver<Message>) -> FlushedMessages<'a, Message> {
     FlushedMessages(receiver)
 }
 
 /// An iterator that yeilds until no more messages are contained in the
 /// `Receiver`'s queue.
-pub struct FlushedMessages<'a, Message: 'a + Send>(&'a Receiver<Message>);
+pub struct FlushedMessages<'a, Message: 'a>(&'a Receiver<Message>);
 
 unsafe impl<'a, Message: 'a + Send> Send for FlushedMessages<'a, Message> {
 }
 
-impl<'a, Message: 'static + Send> Iterator for FlushedMessages<'a, Message> {
+impl<'a, Message: 'static> Iterator for FlushedMessages<'a, Message> {
     type Item = Message;
 
     fn next(&mut self) -> Option<Message> {
@@ -1115,6 +2007,37 @@ impl<'a, Message: 'static + Send> Iterator for FlushedMessages<'a, Message> {
     }
 }
 
+/// A handle that can be sent to other threads in order to wake up the main
+/// thread from `Glfw::wait_events`. It can be obtained by calling
+/// `Glfw::create_wakeup_channel`.
+pub struct WakeupSender<T> {
+    sender: Sender<T>,
+}
+
+impl<T: Send> Clone for WakeupSender<T> {
+    fn clone(&self) -> WakeupSender<T> {
+        WakeupSender { sender: self.sender.clone() }
+    }
+}
+
+impl<T: Send> WakeupSender<T> {
+    /// Wakes up the main thread without sending a message.
+    ///
+    /// Wrapper for `glfwPostEmptyEvent`.
+    pub fn wake(&self) {
+        unsafe { ffi::glfwPostEmptyEvent(); }
+    }
+
+    /// Sends a message to the receiving end of the channel, then wakes up the
+    /// main thread so that it can be handled. An error is returned if the
+    /// `Receiver` has been dropped.
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        try!(self.sender.send(message));
+        self.wake();
+        Ok(())
+    }
+}
+
 /// A struct that wraps a `*GLFWwindow` handle.
 pub struct Window {
     pub ptr: *mut ffi::GLFWwindow,
@@ -1126,6 +2049,15 @@ pub struct Window {
     /// on the `drop_receiver` will result in an `Err(std::comm::Disconnected)`,
     /// indicating that it is safe to drop the `Window`.
     drop_receiver: Receiver<()>,
+    /// The custom cursor attached to the window, kept alive for as long as it
+    /// is in use.
+    current_cursor: Option<Cursor>,
+    /// The standard cursors created by `Window::set_standard_cursor`.
+    standard_cursors: HashMap<StandardCursor, Cursor>,
+    /// The position and size of the window before it was last made
+    /// fullscreen using `Window::set_window_mode`.
+    #[cfg_attr(not(feature = "glfw-3-2"), allow(dead_code))]
+    windowed_geometry: Option<(i32, i32, i32, i32)>,
 }
 
 macro_rules! set_window_callback {
@@ -1157,6 +2089,15 @@ impl Window {
         self.glfw.create_window_intern(width, height, title, mode, Some(self))
     }
 
+    /// Creates a new window sharing its context objects with this window,
+    /// returning the error reported by GLFW if this failed. The error is not
+    /// passed to the error callback.
+    ///
+    /// Wrapper for `glfwCreateWindow`.
+    pub fn try_create_shared(&self, width: u32, height: u32, title: &str, mode: WindowMode) -> Result<(Window, Receiver<(f64, WindowEvent)>), GlfwError> {
+        self.glfw.try_create_window_intern(width, height, title, mode, Some(self))
+    }
+
     /// Calling this method forces the destructor to be called, closing the
     /// window.
     pub fn close(self) {}
@@ -1192,6 +2133,22 @@ impl Window {
         }
     }
 
+    /// Sets the icon of the window. The system picks the image closest to the
+    /// size it needs, so several resolutions can be supplied. Passing an empty
+    /// slice reverts to the default icon.
+    ///
+    /// Wrapper for `glfwSetWindowIcon`. Requires GLFW 3.2.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixels of an image do not contain exactly
+    /// `width * height * 4` bytes.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn set_icon(&mut self, images: &[PixelImage]) {
+        let glfw_images: Vec<ffi::GLFWimage> = images.iter().map(|image| image.to_glfw_image()).collect();
+        unsafe { ffi::glfwSetWindowIcon(self.ptr, glfw_images.len() as c_int, glfw_images.as_ptr()); }
+    }
+
     /// Wrapper for `glfwGetWindowPos`.
     pub fn get_pos(&self) -> (i32, i32) {
         unsafe {
@@ -1222,6 +2179,39 @@ impl Window {
         unsafe { ffi::glfwSetWindowSize(self.ptr, width as c_int, height as c_int); }
     }
 
+    /// Sets the minimum and maximum size of the content area of the window.
+    /// A limit of `None` leaves that side unconstrained.
+    ///
+    /// Wrapper for `glfwSetWindowSizeLimits`. Requires GLFW 3.2.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn set_size_limits(&mut self, min: Option<(u32, u32)>, max: Option<(u32, u32)>) {
+        unsafe {
+            ffi::glfwSetWindowSizeLimits(
+                self.ptr,
+                dont_care_or(min.map(|(width, _)| width)),
+                dont_care_or(min.map(|(_, height)| height)),
+                dont_care_or(max.map(|(width, _)| width)),
+                dont_care_or(max.map(|(_, height)| height)),
+            );
+        }
+    }
+
+    /// Locks the aspect ratio of the content area of the window to the
+    /// supplied `(numerator, denominator)` pair, for example `Some((16, 9))`.
+    /// Passing `None` removes the constraint.
+    ///
+    /// Wrapper for `glfwSetWindowAspectRatio`. Requires GLFW 3.2.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn set_aspect_ratio(&mut self, ratio: Option<(u32, u32)>) {
+        unsafe {
+            ffi::glfwSetWindowAspectRatio(
+                self.ptr,
+                dont_care_or(ratio.map(|(numer, _)| numer)),
+                dont_care_or(ratio.map(|(_, denom)| denom)),
+            );
+        }
+    }
+
     /// Wrapper for `glfwGetFramebufferSize`.
     pub fn get_framebuffer_size(&self) -> (i32, i32) {
         unsafe {
@@ -1232,11 +2222,28 @@ impl Window {
         }
     }
 
+    /// Wrapper for `glfwGetWindowContentScale`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_content_scale(&self) -> (f32, f32) {
+        unsafe {
+            let mut xscale = 0.0;
+            let mut yscale = 0.0;
+            ffi::glfwGetWindowContentScale(self.ptr, &mut xscale, &mut yscale);
+            (xscale as f32, yscale as f32)
+        }
+    }
+
     /// Wrapper for `glfwIconifyWindow`.
     pub fn iconify(&mut self) {
         unsafe { ffi::glfwIconifyWindow(self.ptr); }
     }
 
+    /// Wrapper for `glfwMaximizeWindow`. Requires GLFW 3.2.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn maximize(&mut self) {
+        unsafe { ffi::glfwMaximizeWindow(self.ptr); }
+    }
+
     /// Wrapper for `glfwRestoreWindow`.
     pub fn restore(&mut self) {
         unsafe { ffi::glfwRestoreWindow(self.ptr); }
@@ -1275,6 +2282,71 @@ impl Window {
         }
     }
 
+    /// Switches the window between fullscreen and windowed mode, or moves it to
+    /// another monitor, without recreating the window or its context.
+    ///
+    /// When switching to fullscreen, `pos` is ignored, `size` defaults to the
+    /// current video mode of the monitor and `refresh_rate` defaults to the
+    /// highest available one. When switching back to windowed mode, `pos` and
+    /// `size` default to the position and size the window had before it was
+    /// made fullscreen, and `refresh_rate` is ignored.
+    ///
+    /// # Example
+    ///
+    /// ~~~ignore
+    /// // toggle fullscreen
+    /// if window.with_window_mode(|mode| match mode { glfw::WindowMode::Windowed => true, _ => false }) {
+    ///     glfw.with_primary_monitor(|_, m| {
+    ///         window.set_window_mode(glfw::WindowMode::FullScreen(m.unwrap()), None, None, None);
+    ///     });
+    /// } else {
+    ///     window.set_window_mode(glfw::WindowMode::Windowed, None, None, None);
+    /// }
+    /// ~~~
+    ///
+    /// Wrapper for `glfwSetWindowMonitor`. Requires GLFW 3.2.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn set_window_mode(&mut self, mode: WindowMode, pos: Option<(i32, i32)>, size: Option<(u32, u32)>, refresh_rate: Option<u32>) {
+        let is_fullscreen = unsafe { !ffi::glfwGetWindowMonitor(self.ptr).is_null() };
+        match mode {
+            WindowMode::FullScreen(monitor) => {
+                if !is_fullscreen {
+                    let (xpos, ypos) = self.get_pos();
+                    let (width, height) = self.get_size();
+                    self.windowed_geometry = Some((xpos, ypos, width, height));
+                }
+                let (width, height) = match (size, monitor.get_video_mode()) {
+                    (Some((width, height)), _) => (width as c_int, height as c_int),
+                    (None, Some(vid_mode))     => (vid_mode.width as c_int, vid_mode.height as c_int),
+                    (None, None)               => {
+                        let (width, height) = self.get_size();
+                        (width as c_int, height as c_int)
+                    }
+                };
+                unsafe {
+                    ffi::glfwSetWindowMonitor(self.ptr, monitor.ptr, 0, 0, width, height,
+                                              dont_care_or(refresh_rate));
+                }
+            }
+            WindowMode::Windowed => {
+                let (xpos, ypos, width, height) = match (is_fullscreen, self.windowed_geometry) {
+                    (true, Some(geometry)) => geometry,
+                    _ => {
+                        let (xpos, ypos) = self.get_pos();
+                        let (width, height) = self.get_size();
+                        (xpos, ypos, width, height)
+                    }
+                };
+                let (xpos, ypos) = pos.unwrap_or((xpos, ypos));
+                let (width, height) = size.map_or((width, height), |(width, height)| (width as i32, height as i32));
+                unsafe {
+                    ffi::glfwSetWindowMonitor(self.ptr, ptr::null_mut(), xpos as c_int, ypos as c_int,
+                                              width as c_int, height as c_int, ffi::DONT_CARE);
+                }
+            }
+        }
+    }
+
     /// Wrapper for `glfwGetWindowAttrib` called with `FOCUSED`.
     pub fn is_focused(&self) -> bool {
         unsafe { ffi::glfwGetWindowAttrib(self.ptr, ffi::FOCUSED) == ffi::TRUE }
@@ -1285,6 +2357,13 @@ impl Window {
         unsafe { ffi::glfwGetWindowAttrib(self.ptr, ffi::ICONIFIED) == ffi::TRUE }
     }
 
+    /// Wrapper for `glfwGetWindowAttrib` called with `MAXIMIZED`. Requires
+    /// GLFW 3.2.
+    #[cfg(feature = "glfw-3-2")]
+    pub fn is_maximized(&self) -> bool {
+        unsafe { ffi::glfwGetWindowAttrib(self.ptr, ffi::MAXIMIZED) == ffi::TRUE }
+    }
+
     /// Wrapper for `glfwGetWindowAttrib` called with `CLIENT_API`.
     pub fn get_client_api(&self) -> c_int {
         unsafe { ffi::glfwGetWindowAttrib(self.ptr, ffi::CLIENT_API) }
@@ -1358,12 +2437,25 @@ impl Window {
         self.set_framebuffer_size_polling(should_poll);
         self.set_key_polling(should_poll);
         self.set_char_polling(should_poll);
+        self.set_char_mods_polling(should_poll);
         self.set_mouse_button_polling(should_poll);
         self.set_cursor_pos_polling(should_poll);
         self.set_cursor_enter_polling(should_poll);
         self.set_scroll_polling(should_poll);
+        self.set_drop_polling(should_poll);
+        self.set_glfw_3_3_polling(should_poll);
+    }
+
+    /// Sets the polling of the events that require GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    fn set_glfw_3_3_polling(&mut self, should_poll: bool) {
+        self.set_maximize_polling(should_poll);
+        self.set_content_scale_polling(should_poll);
     }
 
+    #[cfg(not(feature = "glfw-3-3"))]
+    fn set_glfw_3_3_polling(&mut self, _: bool) {}
+
     /// Wrapper for `glfwSetWindowSizeCallback`.
     pub fn set_size_polling(&mut self, should_poll: bool) {
         set_window_callback!(self, should_poll, glfwSetWindowSizeCallback, window_size_callback);
@@ -1389,11 +2481,23 @@ impl Window {
         set_window_callback!(self, should_poll, glfwSetWindowIconifyCallback, window_iconify_callback);
     }
 
+    /// Wrapper for `glfwSetWindowMaximizeCallback`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn set_maximize_polling(&mut self, should_poll: bool) {
+        set_window_callback!(self, should_poll, glfwSetWindowMaximizeCallback, window_maximize_callback);
+    }
+
     /// Wrapper for `glfwSetFramebufferSizeCallback`.
     pub fn set_framebuffer_size_polling(&mut self, should_poll: bool) {
         set_window_callback!(self, should_poll, glfwSetFramebufferSizeCallback, framebuffer_size_callback);
     }
 
+    /// Wrapper for `glfwSetWindowContentScaleCallback`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn set_content_scale_polling(&mut self, should_poll: bool) {
+        set_window_callback!(self, should_poll, glfwSetWindowContentScaleCallback, window_content_scale_callback);
+    }
+
     /// Wrapper for `glfwGetInputMode` called with `CURSOR`.
     pub fn get_cursor_mode(&self) -> CursorMode {
         unsafe { mem::transmute(ffi::glfwGetInputMode(self.ptr, ffi::CURSOR)) }
@@ -1404,6 +2508,39 @@ impl Window {
         unsafe { ffi::glfwSetInputMode(self.ptr, ffi::CURSOR, mode as c_int); }
     }
 
+    /// Attaches a custom cursor to the window, or reverts to the default
+    /// cursor if `None` is given. The window takes ownership of the cursor so
+    /// that it cannot be destroyed while in use. The previously attached
+    /// cursor is returned so that it can be reused.
+    ///
+    /// Wrapper for `glfwSetCursor`.
+    pub fn set_cursor(&mut self, cursor: Option<Cursor>) -> Option<Cursor> {
+        let ptr = cursor.as_ref().map_or(ptr::null_mut(), |c| c.ptr);
+        unsafe { ffi::glfwSetCursor(self.ptr, ptr); }
+        mem::replace(&mut self.current_cursor, cursor)
+    }
+
+    /// Switches the window to a standard cursor shape. Each shape is only
+    /// created the first time it is requested and is then cached for the
+    /// lifetime of the window, so this is cheap enough to call every frame. If
+    /// the shape could not be created the default cursor is used instead.
+    ///
+    /// Returns the custom cursor that was previously attached using
+    /// `Window::set_cursor`, if any.
+    ///
+    /// Wrapper for `glfwCreateStandardCursor` and `glfwSetCursor`.
+    pub fn set_standard_cursor(&mut self, shape: StandardCursor) -> Option<Cursor> {
+        if !self.standard_cursors.contains_key(&shape) {
+            match self.glfw.create_standard_cursor(shape) {
+                Some(cursor) => { self.standard_cursors.insert(shape, cursor); },
+                None         => {},
+            }
+        }
+        let ptr = self.standard_cursors.get(&shape).map_or(ptr::null_mut(), |c| c.ptr);
+        unsafe { ffi::glfwSetCursor(self.ptr, ptr); }
+        self.current_cursor.take()
+    }
+
     /// Wrapper for `glfwGetInputMode` called with `STICKY_KEYS`.
     pub fn has_sticky_keys(&self) -> bool {
         unsafe { ffi::glfwGetInputMode(self.ptr, ffi::STICKY_KEYS) == ffi::TRUE }
@@ -1459,6 +2596,11 @@ impl Window {
         set_window_callback!(self, should_poll, glfwSetCharCallback, char_callback);
     }
 
+    /// Wrapper for `glfwSetCharModsCallback`.
+    pub fn set_char_mods_polling(&mut self, should_poll: bool) {
+        set_window_callback!(self, should_poll, glfwSetCharModsCallback, char_mods_callback);
+    }
+
     /// Wrapper for `glfwSetMouseButtonCallback`.
     pub fn set_mouse_button_polling(&mut self, should_poll: bool) {
         set_window_callback!(self, should_poll, glfwSetMouseButtonCallback, mouse_button_callback);
@@ -1479,6 +2621,11 @@ impl Window {
         set_window_callback!(self, should_poll, glfwSetScrollCallback, scroll_callback);
     }
 
+    /// Wrapper for `glfwSetDropCallback`.
+    pub fn set_drop_polling(&mut self, should_poll: bool) {
+        set_window_callback!(self, should_poll, glfwSetDropCallback, drop_callback);
+    }
+
     /// Wrapper for `glfwGetClipboardString`.
     pub fn set_clipboard_string(&mut self, string: &str) {
         unsafe {
@@ -1635,6 +2782,14 @@ pub enum JoystickId {
     Joystick16      = ffi::JOYSTICK_16,
 }
 
+/// Joystick events.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum JoystickEvent {
+    Connected                   = ffi::CONNECTED,
+    Disconnected                = ffi::DISCONNECTED,
+}
+
 /// A joystick handle.
 #[derive(Copy)]
 pub struct Joystick {
@@ -1657,6 +2812,22 @@ impl Joystick {
         }
     }
 
+    /// Like `get_axes`, but replaces the contents of `axes` instead of
+    /// allocating a new vector.
+    ///
+    /// Wrapper for `glfwGetJoystickAxes`.
+    pub fn get_axes_into(&self, axes: &mut Vec<f32>) {
+        axes.clear();
+        unsafe {
+            let mut count = 0;
+            let ptr = ffi::glfwGetJoystickAxes(self.id as c_int, &mut count);
+            if ptr.is_null() {
+                return;
+            }
+            axes.extend(slice::from_raw_parts(ptr, count as usize).iter().map(|&a| a as f32));
+        }
+    }
+
     /// Wrapper for `glfwGetJoystickButtons`.
     pub fn get_buttons(&self) -> Vec<c_int> {
         unsafe {
@@ -1666,8 +2837,355 @@ impl Joystick {
         }
     }
 
+    /// Returns the state of each button, which is either `Action::Press` or
+    /// `Action::Release`.
+    ///
+    /// Wrapper for `glfwGetJoystickButtons`.
+    pub fn get_button_states(&self) -> Vec<Action> {
+        unsafe {
+            let mut count = 0;
+            let ptr = ffi::glfwGetJoystickButtons(self.id as c_int, &mut count);
+            if ptr.is_null() {
+                return Vec::new();
+            }
+            slice::from_raw_parts(ptr, count as usize).iter().map(|&b| mem::transmute(b as c_int)).collect()
+        }
+    }
+
+    /// Like `get_button_states`, but replaces the contents of `buttons`
+    /// instead of allocating a new vector.
+    ///
+    /// Wrapper for `glfwGetJoystickButtons`.
+    pub fn get_button_states_into(&self, buttons: &mut Vec<Action>) {
+        buttons.clear();
+        unsafe {
+            let mut count = 0;
+            let ptr = ffi::glfwGetJoystickButtons(self.id as c_int, &mut count);
+            if ptr.is_null() {
+                return;
+            }
+            buttons.extend(slice::from_raw_parts(ptr, count as usize).iter().map(|&b| mem::transmute(b as c_int)));
+        }
+    }
+
+    /// Wrapper for `glfwGetJoystickHats`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_hats(&self) -> Vec<JoystickHats> {
+        unsafe {
+            let mut count = 0;
+            let ptr = ffi::glfwGetJoystickHats(self.id as c_int, &mut count);
+            if ptr.is_null() {
+                return Vec::new();
+            }
+            slice::from_raw_parts(ptr, count as usize).iter().map(|&h| JoystickHats::from_bits_truncate(h as c_int)).collect()
+        }
+    }
+
+    /// Like `get_hats`, but replaces the contents of `hats` instead of
+    /// allocating a new vector.
+    ///
+    /// Wrapper for `glfwGetJoystickHats`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_hats_into(&self, hats: &mut Vec<JoystickHats>) {
+        hats.clear();
+        unsafe {
+            let mut count = 0;
+            let ptr = ffi::glfwGetJoystickHats(self.id as c_int, &mut count);
+            if ptr.is_null() {
+                return;
+            }
+            hats.extend(slice::from_raw_parts(ptr, count as usize).iter().map(|&h| JoystickHats::from_bits_truncate(h as c_int)));
+        }
+    }
+
     /// Wrapper for `glfwGetJoystickName`.
     pub fn get_name(&self) -> String {
         unsafe { string_from_c_str(ffi::glfwGetJoystickName(self.id as c_int)) }
     }
+
+    /// Returns the SDL compatible GUID of the joystick, which identifies the
+    /// model of the device across reconnects, or `None` if the joystick is
+    /// not present.
+    ///
+    /// Wrapper for `glfwGetJoystickGUID`. Requires GLFW 3.3.
+    #[cfg(feature = "glfw-3-3")]
+    pub fn get_guid(&self) -> Option<String> {
+        unsafe {
+            match ffi::glfwGetJoystickGUID(self.id as c_int) {
+                ptr if ptr.is_null() => None,
+                ptr => Some(string_from_c_str(ptr)),
+            }
+        }
+    }
+}
+
+/// Joystick input events, as synthesized by `JoystickPoller`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum JoystickInputEvent {
+    /// A button was pressed or released. Contains the index of the button.
+    Button(JoystickId, usize, Action),
+    /// An axis moved. Contains the index of the axis and its new value.
+    AxisMoved(JoystickId, usize, f32),
+}
+
+/// Synthesizes joystick button and axis events by comparing the state of
+/// each joystick with the state seen on the previous call to `poll`, as GLFW
+/// does not report joystick input through callbacks.
+///
+/// # Example
+///
+/// ~~~ignore
+/// let (mut poller, joystick_events) = glfw::JoystickPoller::new(&glfw);
+/// poller.set_dead_zone(0.1);
+/// loop {
+///     glfw.poll_events();
+///     poller.poll();
+///     for (time, event) in glfw::flush_messages(&joystick_events) {
+///         println!("{:?}: {:?}", time, event);
+///     }
+/// }
+/// ~~~
+pub struct JoystickPoller {
+    glfw: Glfw,
+    sender: Sender<(f64, JoystickInputEvent)>,
+    dead_zone: f32,
+    axis_threshold: f32,
+    /// The last reported axis values and button states of each joystick,
+    /// indexed by `JoystickId`. Empty for joysticks that are not present.
+    states: Vec<(Vec<f32>, Vec<Action>)>,
+    /// Buffers for the current state of the joystick being polled, reused
+    /// to avoid allocating on every poll.
+    axes: Vec<f32>,
+    buttons: Vec<Action>,
+}
+
+impl JoystickPoller {
+    /// Creates a new poller, along with the receiver its events are sent to.
+    /// The events are timestamped with `Glfw::get_time`.
+    pub fn new(glfw: &Glfw) -> (JoystickPoller, Receiver<(f64, JoystickInputEvent)>) {
+        let (sender, receiver) = channel();
+        let poller = JoystickPoller {
+            glfw: *glfw,
+            sender: sender,
+            dead_zone: 0.0,
+            axis_threshold: 0.0,
+            states: (ffi::JOYSTICK_1..ffi::JOYSTICK_LAST + 1).map(|_| (Vec::new(), Vec::new())).collect(),
+            axes: Vec::new(),
+            buttons: Vec::new(),
+        };
+        (poller, receiver)
+    }
+
+    /// Sets the dead zone of the axes. Axis values closer to zero than this
+    /// are reported as zero. Defaults to `0.0`.
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = dead_zone;
+    }
+
+    /// Sets the minimum change in the value of an axis that is reported as
+    /// an `AxisMoved` event. An axis returning to zero is always reported.
+    /// Defaults to `0.0`, which reports every change.
+    pub fn set_axis_threshold(&mut self, axis_threshold: f32) {
+        self.axis_threshold = axis_threshold;
+    }
+
+    /// Sends events for the changes in joystick input since the last call.
+    /// This should be called after `Glfw::poll_events` or
+    /// `Glfw::wait_events`. When a joystick is disconnected, its held buttons
+    /// are reported as released and its axes as returning to zero.
+    pub fn poll(&mut self) {
+        let time = self.glfw.get_time();
+        for raw_id in ffi::JOYSTICK_1..ffi::JOYSTICK_LAST + 1 {
+            let id: JoystickId = unsafe { mem::transmute(raw_id) };
+            let joystick = self.glfw.get_joystick(id);
+            if joystick.is_present() {
+                joystick.get_axes_into(&mut self.axes);
+                joystick.get_button_states_into(&mut self.buttons);
+                for axis in self.axes.iter_mut() {
+                    if axis.abs() < self.dead_zone {
+                        *axis = 0.0;
+                    }
+                }
+            } else {
+                self.axes.clear();
+                self.buttons.clear();
+            }
+            self.update(time, id);
+        }
+    }
+
+    /// Compares the state of a joystick in `axes` and `buttons` with the
+    /// last one, sending events for the differences. Inputs that are missing
+    /// from either state are treated as being at rest.
+    fn update(&mut self, time: f64, id: JoystickId) {
+        let threshold = self.axis_threshold;
+        let axes = &self.axes;
+        let buttons = &self.buttons;
+        let (ref mut last_axes, ref mut last_buttons) = self.states[id as usize];
+
+        for i in 0..std::cmp::max(axes.len(), last_axes.len()) {
+            let value = axes.get(i).cloned().unwrap_or(0.0);
+            let mut last = last_axes.get(i).cloned().unwrap_or(0.0);
+            if value != last && ((value - last).abs() >= threshold || value == 0.0) {
+                last = value;
+                let _ = self.sender.send((time, JoystickInputEvent::AxisMoved(id, i, value)));
+            }
+            if i < last_axes.len() {
+                last_axes[i] = last;
+            } else if i < axes.len() {
+                last_axes.push(last);
+            }
+        }
+        last_axes.truncate(axes.len());
+
+        for i in 0..std::cmp::max(buttons.len(), last_buttons.len()) {
+            let action = buttons.get(i).cloned().unwrap_or(Action::Release);
+            let last = last_buttons.get(i).cloned().unwrap_or(Action::Release);
+            if action != last {
+                let _ = self.sender.send((time, JoystickInputEvent::Button(id, i, action)));
+            }
+        }
+        last_buttons.clear();
+        last_buttons.extend(buttons.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::Receiver;
+    use super::{Action, GammaRamp, GammaRampError, Glfw, JoystickId, JoystickInputEvent, JoystickPoller};
+
+    #[test]
+    fn gamma_ramp_validation() {
+        let empty = GammaRamp { red: vec![], green: vec![], blue: vec![] };
+        assert_eq!(empty.validate(), Err(GammaRampError::Empty));
+        let mismatched = GammaRamp { red: vec![0, 1], green: vec![0, 1], blue: vec![0] };
+        assert_eq!(mismatched.validate(), Err(GammaRampError::MismatchedLengths));
+        assert_eq!(GammaRamp::identity(256).validate(), Ok(()));
+    }
+
+    #[test]
+    fn gamma_ramp_identity() {
+        let ramp = GammaRamp::identity(256);
+        assert_eq!(ramp.red.len(), 256);
+        assert_eq!((ramp.red[0], ramp.red[255]), (0, 65535));
+        assert_eq!((ramp.green[0], ramp.green[255]), (0, 65535));
+        assert_eq!((ramp.blue[0], ramp.blue[255]), (0, 65535));
+        assert_eq!(ramp.red[128], 32896);
+    }
+
+    #[test]
+    fn gamma_ramp_unit_gamma_is_identity() {
+        for &size in [1, 2, 256, 1024].iter() {
+            let gamma = GammaRamp::from_gamma(size, 1.0);
+            let identity = GammaRamp::identity(size);
+            assert_eq!(gamma.red, identity.red);
+            assert_eq!(gamma.green, identity.green);
+            assert_eq!(gamma.blue, identity.blue);
+        }
+    }
+
+    #[test]
+    fn gamma_ramp_brightness_contrast_clamping() {
+        let bright = GammaRamp::from_brightness_contrast(256, 0.5, 1.0);
+        assert_eq!(bright.red[0], 32768);
+        assert!(bright.red[128..].iter().all(|&v| v == 65535));
+
+        let dark = GammaRamp::from_brightness_contrast(256, -0.5, 1.0);
+        assert!(dark.red[..128].iter().all(|&v| v == 0));
+        assert_eq!(dark.red[255], 32768);
+
+        let contrast = GammaRamp::from_brightness_contrast(256, 0.0, 4.0);
+        assert!(contrast.red[..64].iter().all(|&v| v == 0));
+        assert!(contrast.red[192..].iter().all(|&v| v == 65535));
+        assert_eq!(contrast.red, contrast.blue);
+    }
+
+    fn events(receiver: &Receiver<(f64, JoystickInputEvent)>) -> Vec<JoystickInputEvent> {
+        let mut events = Vec::new();
+        while let Ok((_, event)) = receiver.try_recv() {
+            events.push(event);
+        }
+        events
+    }
+
+    #[test]
+    fn joystick_poller_press_and_release() {
+        let id = JoystickId::Joystick1;
+        let (mut poller, receiver) = JoystickPoller::new(&Glfw);
+        poller.buttons = vec![Action::Release, Action::Press];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::Button(id, 1, Action::Press)]);
+
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![]);
+
+        poller.buttons = vec![Action::Release, Action::Release];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::Button(id, 1, Action::Release)]);
+    }
+
+    #[test]
+    fn joystick_poller_release_on_disconnect() {
+        let id = JoystickId::Joystick2;
+        let (mut poller, receiver) = JoystickPoller::new(&Glfw);
+        poller.buttons = vec![Action::Press, Action::Release, Action::Press];
+        poller.update(0.0, id);
+        events(&receiver);
+
+        poller.buttons = vec![Action::Press];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::Button(id, 2, Action::Release)]);
+
+        poller.buttons.clear();
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::Button(id, 0, Action::Release)]);
+    }
+
+    #[test]
+    fn joystick_poller_axis_threshold() {
+        let id = JoystickId::Joystick1;
+        let (mut poller, receiver) = JoystickPoller::new(&Glfw);
+        poller.set_axis_threshold(0.25);
+        poller.axes = vec![0.1];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![]);
+
+        poller.axes = vec![0.5];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 0, 0.5)]);
+
+        poller.axes = vec![0.625];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![]);
+
+        poller.axes = vec![0.125];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 0, 0.125)]);
+
+        poller.axes = vec![0.0];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 0, 0.0)]);
+    }
+
+    #[test]
+    fn joystick_poller_axis_count_changes() {
+        let id = JoystickId::Joystick3;
+        let (mut poller, receiver) = JoystickPoller::new(&Glfw);
+        poller.axes = vec![0.5];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 0, 0.5)]);
+
+        poller.axes = vec![0.5, 0.0, -1.0];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 2, -1.0)]);
+
+        poller.axes = vec![0.5];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 2, 0.0)]);
+
+        poller.axes = vec![0.5, 0.0, -1.0];
+        poller.update(0.0, id);
+        assert_eq!(events(&receiver), vec![JoystickInputEvent::AxisMoved(id, 2, -1.0)]);
+    }
 }